@@ -0,0 +1,18 @@
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::CowVec;
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for CowVec<T> {
+    /// Generates a `CowVec` with a fresh arena from unstructured input.
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Vec::<T>::arbitrary(u).map(CowVec::from)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Vec::<T>::arbitrary_take_rest(u).map(CowVec::from)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Vec::<T>::size_hint(depth)
+    }
+}
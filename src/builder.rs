@@ -0,0 +1,131 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use super::CowVec;
+
+/// Configures and creates an empty [`CowVec`] with an optional
+/// auto-compaction policy.
+///
+/// # Example
+/// ```
+/// use cow_vec::CowVecBuilder;
+///
+/// let mut vec = CowVecBuilder::<i32>::new()
+///     .items_capacity(16)
+///     .arena_capacity(256)
+///     .auto_compact_threshold(64)
+///     .build();
+/// vec.push(1);
+/// let snapshot = vec.clone();
+/// assert_eq!(snapshot.to_vec(), vec![1]);
+/// ```
+pub struct CowVecBuilder<T> {
+    items_capacity: usize,
+    arena_capacity: usize,
+    auto_compact_threshold: Option<usize>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> CowVecBuilder<T> {
+    /// Creates a builder with zero capacities and no auto-compaction.
+    pub fn new() -> Self {
+        Self {
+            items_capacity: 0,
+            arena_capacity: 0,
+            auto_compact_threshold: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the initial capacity of the pointer vector.
+    pub fn items_capacity(mut self, capacity: usize) -> Self {
+        self.items_capacity = capacity;
+        self
+    }
+
+    /// Sets the initial capacity of the arena.
+    pub fn arena_capacity(mut self, capacity: usize) -> Self {
+        self.arena_capacity = capacity;
+        self
+    }
+
+    /// Makes `clone()` on the built vector behave like
+    /// [`clone_with_max_capacity(threshold)`](CowVec::clone_with_max_capacity).
+    pub fn auto_compact_threshold(mut self, threshold: usize) -> Self {
+        self.auto_compact_threshold = Some(threshold);
+        self
+    }
+
+    /// Creates the empty vector, carrying the configured compaction policy.
+    pub fn build(self) -> CompactingCowVec<T> {
+        CompactingCowVec {
+            vec: CowVec::with_capacities(self.items_capacity, self.arena_capacity),
+            threshold: self.auto_compact_threshold,
+        }
+    }
+}
+
+impl<T> Default for CowVecBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`CowVec`] that applies an auto-compaction policy when cloned.
+///
+/// Created by [`CowVecBuilder::build`]. It dereferences to the inner
+/// `CowVec`, so the whole `CowVec` API is available. Only `clone()` differs:
+/// with a threshold set, a clone whose arena holds more allocations than the
+/// threshold gets a fresh, compacted arena instead of sharing the old one.
+pub struct CompactingCowVec<T> {
+    vec: CowVec<T>,
+    threshold: Option<usize>,
+}
+
+impl<T> CompactingCowVec<T> {
+    /// Returns the auto-compaction threshold, if any.
+    pub fn threshold(&self) -> Option<usize> {
+        self.threshold
+    }
+
+    /// Unwraps the inner `CowVec`, dropping the policy.
+    pub fn into_inner(self) -> CowVec<T> {
+        self.vec
+    }
+}
+
+impl<T> Deref for CompactingCowVec<T> {
+    type Target = CowVec<T>;
+
+    fn deref(&self) -> &CowVec<T> {
+        &self.vec
+    }
+}
+
+impl<T> DerefMut for CompactingCowVec<T> {
+    fn deref_mut(&mut self) -> &mut CowVec<T> {
+        &mut self.vec
+    }
+}
+
+impl<T: Clone> Clone for CompactingCowVec<T> {
+    /// Clones the vector, compacting into a fresh arena if the arena exceeds
+    /// the threshold. Without a threshold this is an O(1) `CowVec` clone.
+    fn clone(&self) -> Self {
+        let vec = match self.threshold {
+            Some(threshold) => self.vec.clone_with_max_capacity(threshold),
+            None => self.vec.clone(),
+        };
+        Self {
+            vec,
+            threshold: self.threshold,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CompactingCowVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.vec.fmt(f)
+    }
+}
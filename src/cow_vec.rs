@@ -1,47 +1,266 @@
+use std::borrow::{Borrow, Cow};
+use std::cell::Cell;
+use std::collections::{HashMap, TryReserveError};
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
 use std::ops::{Bound, Index, IndexMut, RangeBounds};
-use std::sync::{Arc, Mutex};
+use std::ptr::NonNull;
+use std::sync::{Arc, Mutex, MutexGuard};
 
+#[cfg(feature = "rand")]
+use rand::seq::{index, IndexedRandom, SliceRandom};
+#[cfg(feature = "rand")]
+use rand::Rng;
 use typed_arena::Arena;
 
-use super::CowVecIter;
+use super::{ChunkBy, CowVecIter, Entry, RSplit, RSplitN, Split, SplitN};
 
 /// Shared arena that stores values allocated by `CowVec` instances.
 ///
 /// The arena is append-only: values are never removed or moved once allocated.
 /// This guarantees that pointers to arena items remain valid for the arena's lifetime.
 struct CowArena<T> {
-    arena: Mutex<Arena<T>>,
+    arena: Mutex<ArenaState<T>>,
+}
+
+/// The arena's values together with the capacity of its current chunk.
+///
+/// `typed_arena` grows by allocating a chunk of
+/// `max(2 * current_capacity, additional.next_power_of_two())` values but does
+/// not expose the current capacity, so it is tracked here for `try_reserve`.
+struct ArenaState<T> {
+    values: Arena<T>,
+    chunk_capacity: Cell<usize>,
+}
+
+impl<T> ArenaState<T> {
+    fn new(values: Arena<T>) -> Self {
+        // A fresh arena's first chunk is entirely unused.
+        let chunk_capacity = Cell::new(values.uninitialized_array().len());
+        Self {
+            values,
+            chunk_capacity,
+        }
+    }
+
+    /// Returns the number of values the current chunk still has room for.
+    fn unused(&self) -> usize {
+        self.values.uninitialized_array().len()
+    }
+
+    /// Starts tracking an allocation so that `chunk_capacity` follows any
+    /// chunk it creates.
+    fn track_growth(&self) -> GrowthTracker<'_, T> {
+        GrowthTracker {
+            state: self,
+            unused_before: self.unused(),
+            allocated: Cell::new(0),
+        }
+    }
+}
+
+/// Updates [`ArenaState::chunk_capacity`] when dropped, including when the
+/// iterator passed to an allocation panics.
+///
+/// `typed_arena` places a batch either entirely in the current chunk or
+/// entirely in a new one, and a new chunk always has more room than the old
+/// one had left. So a new chunk shows up as unused space that does not match
+/// the old unused space minus the values allocated.
+struct GrowthTracker<'a, T> {
+    state: &'a ArenaState<T>,
+    unused_before: usize,
+    allocated: Cell<usize>,
+}
+
+impl<T> GrowthTracker<'_, T> {
+    fn record(&self, count: usize) {
+        self.allocated.set(self.allocated.get() + count);
+    }
+}
+
+impl<T> Drop for GrowthTracker<'_, T> {
+    fn drop(&mut self) {
+        let unused = self.state.unused();
+        let allocated = self.allocated.get();
+        if self.unused_before.checked_sub(allocated) != Some(unused) {
+            self.state.chunk_capacity.set(unused + allocated);
+        }
+    }
 }
 
 impl<T> CowArena<T> {
     fn new() -> Self {
         Self {
-            arena: Mutex::new(Arena::new()),
+            arena: Mutex::new(ArenaState::new(Arena::new())),
         }
     }
 
     fn with_capacity(capacity: usize) -> Self {
         Self {
-            arena: Mutex::new(Arena::with_capacity(capacity)),
+            arena: Mutex::new(ArenaState::new(Arena::with_capacity(capacity))),
         }
     }
 
-    /// Allocates a value in the arena and returns a raw pointer to it.
+    /// Locks the arena, recovering from mutex poisoning.
+    ///
+    /// A panic while the lock is held cannot leave the arena in an inconsistent
+    /// state: it is append-only and existing values are never touched, so it is
+    /// safe to keep using it after another thread panicked.
+    fn lock(&self) -> MutexGuard<'_, ArenaState<T>> {
+        self.arena.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Allocates a value in the arena and returns a pointer to it.
     ///
     /// # Safety
     /// The returned pointer is valid for the lifetime of the arena.
     /// Since the arena is append-only and wrapped in Arc, the pointer
     /// remains valid as long as any CowVec holds a reference to this arena.
-    fn alloc(&self, value: T) -> *const T {
-        let arena = self.arena.lock().unwrap();
-        let reference = arena.alloc(value);
-        reference as *const T
+    fn alloc(&self, value: T) -> NonNull<T> {
+        let state = self.lock();
+        let growth = state.track_growth();
+        let reference = state.values.alloc(value);
+        growth.record(1);
+        NonNull::from(reference)
+    }
+
+    /// Allocates all values from the iterator in the arena and returns pointers to them.
+    ///
+    /// The mutex is locked once for the whole batch rather than once per value.
+    /// The iterator runs while the lock is held, so it must not allocate in
+    /// this same arena.
+    ///
+    /// # Safety
+    /// The returned pointers are valid for the lifetime of the arena (see `alloc`).
+    fn alloc_extend<I: IntoIterator<Item = T>>(&self, iter: I) -> Vec<NonNull<T>> {
+        let state = self.lock();
+        let growth = state.track_growth();
+        let values = iter.into_iter().inspect(|_| growth.record(1));
+        state
+            .values
+            .alloc_extend(values)
+            .iter_mut()
+            .map(NonNull::from)
+            .collect()
+    }
+
+    /// Reserves contiguous room for `additional` more values, returning an
+    /// error instead of aborting if the allocator cannot provide it.
+    ///
+    /// `typed_arena` only grows infallibly, so when the current chunk lacks
+    /// room, the allocator is first probed with a fallible allocation of
+    /// exactly the chunk the arena is about to allocate. The arena only grows
+    /// if that probe succeeds.
+    fn try_reserve(&self, additional: usize) -> Result<(), TryReserveError> {
+        let state = self.lock();
+        if additional <= state.unused() {
+            return Ok(());
+        }
+        // An overflowing size makes the probe report a capacity overflow
+        // instead of letting `typed_arena` panic.
+        let new_capacity = state
+            .chunk_capacity
+            .get()
+            .checked_mul(2)
+            .zip(additional.checked_next_power_of_two())
+            .map_or(usize::MAX, |(doubled, required)| doubled.max(required));
+        Vec::<T>::new().try_reserve_exact(new_capacity)?;
+        let _growth = state.track_growth();
+        state.values.reserve_extend(additional);
+        Ok(())
     }
 
     /// Returns the total number of allocations in this arena.
     fn len(&self) -> usize {
-        self.arena.lock().unwrap().len()
+        self.lock().values.len()
+    }
+}
+
+/// How [`CowArena::into_values`] obtains one value, as staged by
+/// [`CowArena::stage_values`].
+enum StagedValue<T> {
+    /// Move the value out of this arena slot (in allocation order).
+    Move(usize),
+    /// Use this clone; the slot itself is moved out for a later pointer.
+    Cloned(T),
+}
+
+impl<T: Clone> CowArena<T> {
+    /// Runs every `clone` needed to move out the values referenced by `ptrs`.
+    ///
+    /// Values that are referenced more than once are cloned for all but their
+    /// last occurrence. Nothing is moved yet, so a panicking `clone` leaves the
+    /// arena and every pointer into it intact.
+    fn stage_values(&self, ptrs: &[NonNull<T>]) -> Vec<StagedValue<T>> {
+        let mut state = self.lock();
+        let arena = &mut state.values;
+
+        if std::mem::size_of::<T>() == 0 {
+            // Zero-sized values all share one address, so slots cannot be told
+            // apart by pointer. They are interchangeable: take them in
+            // allocation order and clone one for pointers past the arena's length.
+            let allocated = arena.len();
+            return ptrs
+                .iter()
+                .enumerate()
+                .map(|(index, ptr)| {
+                    if index < allocated {
+                        StagedValue::Move(index)
+                    } else {
+                        // SAFETY: Same as get() - pointer is valid for arena's lifetime
+                        StagedValue::Cloned(unsafe { ptr.as_ref() }.clone())
+                    }
+                })
+                .collect();
+        }
+
+        // Each allocation's address, in allocation order, which is also the
+        // order of `Arena::into_vec`.
+        let allocations: Vec<NonNull<T>> = arena.iter_mut().map(NonNull::from).collect();
+        let slots: HashMap<NonNull<T>, usize> = allocations
+            .iter()
+            .enumerate()
+            .map(|(slot, &ptr)| (ptr, slot))
+            .collect();
+        let indices: Vec<usize> = ptrs.iter().map(|ptr| slots[ptr]).collect();
+
+        let mut remaining = vec![0usize; allocations.len()];
+        for &slot in &indices {
+            remaining[slot] += 1;
+        }
+
+        indices
+            .into_iter()
+            .map(|slot| {
+                remaining[slot] -= 1;
+                if remaining[slot] == 0 {
+                    StagedValue::Move(slot)
+                } else {
+                    // SAFETY: The pointer was just taken from the locked arena.
+                    StagedValue::Cloned(unsafe { allocations[slot].as_ref() }.clone())
+                }
+            })
+            .collect()
+    }
+
+    /// Consumes the arena and returns the staged values in order, along with
+    /// every value that was not moved out.
+    ///
+    /// No user code runs here. The leftover values are returned rather than
+    /// dropped so that callers can restore a consistent state before their
+    /// destructors run.
+    fn into_values(self, staged: Vec<StagedValue<T>>) -> (Vec<T>, Vec<T>) {
+        let state = self.arena.into_inner().unwrap_or_else(|e| e.into_inner());
+        let mut values: Vec<Option<T>> = state.values.into_vec().into_iter().map(Some).collect();
+        let live = staged
+            .into_iter()
+            .map(|value| match value {
+                StagedValue::Move(slot) => values[slot].take().unwrap(),
+                StagedValue::Cloned(value) => value,
+            })
+            .collect();
+        (live, values.into_iter().flatten().collect())
     }
 }
 
@@ -71,27 +290,127 @@ impl<T> CowArena<T> {
 /// ```
 pub struct CowVec<T> {
     arena: Arc<CowArena<T>>,
-    items: Arc<Vec<*const T>>,
+    items: Arc<Vec<NonNull<T>>>,
 }
 
 // SAFETY: CowVec is Send+Sync because:
-// - Arc<CowArena<T>> is Send+Sync when T: Send+Sync (CowArena contains Mutex<Arena<T>>)
-// - *const T pointers are valid as long as arena lives (guaranteed by Arc)
-// - All mutation goes through Mutex
-// - We only provide &T access, never &mut T
+// - Arc<CowArena<T>> is Send+Sync when T: Send+Sync (CowArena contains Mutex<ArenaState<T>>)
+// - NonNull<T> pointers are valid as long as arena lives (guaranteed by Arc)
+// - Allocating new values goes through the Mutex
+// - Shared access only ever yields &T
+// - &mut T is only handed out through &mut self, and only for slots that were
+//   just allocated by that call (get2_mut, iter_mut_enumerated), so no other
+//   vector, handle, or index of this vector can reach them
+// - set_in_place and try_get_mut write to an existing slot outside the Mutex,
+//   but only through &mut self and only when uniquely_owned_slot holds: this
+//   vector is the sole Arc owner of the arena (no clone or ElemHandle on any
+//   thread can reach it) and the slot's pointer is not repeated in items, so
+//   no &T to that value can be alive
 unsafe impl<T: Send + Sync> Send for CowVec<T> {}
 unsafe impl<T: Send + Sync> Sync for CowVec<T> {}
 
+/// A stable handle to a single element of a `CowVec`.
+///
+/// The handle points directly at the element's slot in the arena and keeps the
+/// arena alive, so it stays valid regardless of later `push`, `set`, or
+/// structural mutations on the originating vector (or the vector being dropped).
+///
+/// # Example
+/// ```
+/// use cow_vec::CowVec;
+///
+/// let mut vec = CowVec::from(vec![1, 2, 3]);
+/// let handle = vec.handle(0).unwrap();
+/// vec.set(0, 10);
+/// assert_eq!(*handle.get(), 1);
+/// assert_eq!(vec[0], 10);
+/// ```
+pub struct ElemHandle<T> {
+    arena: Arc<CowArena<T>>,
+    ptr: NonNull<T>,
+}
+
+// SAFETY: ElemHandle is Send+Sync for the same reasons as CowVec: it holds an
+// Arc to the arena and only ever provides &T access to the pointed-to value.
+unsafe impl<T: Send + Sync> Send for ElemHandle<T> {}
+unsafe impl<T: Send + Sync> Sync for ElemHandle<T> {}
+
+impl<T> ElemHandle<T> {
+    /// Returns a reference to the element this handle points to.
+    pub fn get(&self) -> &T {
+        // SAFETY: The pointer was obtained from the arena, which this handle
+        // keeps alive via Arc and which never moves or deallocates items.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> Clone for ElemHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            arena: Arc::clone(&self.arena),
+            ptr: self.ptr,
+        }
+    }
+}
+
+/// A snapshot of a `CowVec`'s arena usage, returned by [`CowVec::arena_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaStats {
+    /// Number of elements in the vector.
+    pub live: usize,
+    /// Number of values ever allocated in the arena backing the vector.
+    pub allocated: usize,
+    /// Number of arena values not counted as live, i.e. `allocated - live`.
+    pub garbage: usize,
+    /// Whether the arena is shared with other `CowVec` instances.
+    pub arena_shared: bool,
+}
+
 impl<T> CowVec<T> {
     /// Returns a mutable reference to the items vector.
     ///
     /// If the items Arc is shared with other CowVec instances, this will
     /// clone the vector first (copy-on-write semantics).
     #[inline]
-    fn items_mut(&mut self) -> &mut Vec<*const T> {
+    fn items_mut(&mut self) -> &mut Vec<NonNull<T>> {
         Arc::make_mut(&mut self.items)
     }
 
+    /// Poisons the arena mutex by panicking while holding it.
+    #[cfg(test)]
+    pub(crate) fn poison_arena(&self) {
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = self.arena.arena.lock();
+            panic!("poisoning arena");
+        }));
+        assert!(self.arena.arena.is_poisoned());
+    }
+
+    /// Returns the arena's tracked current chunk capacity and its unused room.
+    #[cfg(test)]
+    pub(crate) fn arena_chunk_usage(&self) -> (usize, usize) {
+        let state = self.arena.lock();
+        (state.chunk_capacity.get(), state.unused())
+    }
+
+    /// Converts a range over element indices into `(start, end)` bounds.
+    ///
+    /// Bounds are not validated against `len()`; callers rely on slice
+    /// indexing to panic on out-of-range values.
+    fn resolve_range<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len(),
+        };
+        (start, end)
+    }
+
     /// Creates a new empty `CowVec`.
     pub fn new() -> Self {
         Self {
@@ -108,6 +427,85 @@ impl<T> CowVec<T> {
         }
     }
 
+    /// Creates a new empty `CowVec` with separate capacities for the pointer
+    /// vector and the arena.
+    ///
+    /// Every `set` allocates a fresh arena slot, so a vector expected to see
+    /// heavy editing benefits from an arena larger than its pointer vector.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec: CowVec<i32> = CowVec::with_capacities(16, 1024);
+    /// assert!(vec.capacity() >= 16);
+    /// assert!(vec.is_empty());
+    /// ```
+    pub fn with_capacities(items_capacity: usize, arena_capacity: usize) -> Self {
+        Self {
+            arena: Arc::new(CowArena::with_capacity(arena_capacity)),
+            items: Arc::new(Vec::with_capacity(items_capacity)),
+        }
+    }
+
+    /// Creates a `CowVec` from a `Vec`, sizing the arena for `arena_extra`
+    /// further allocations.
+    ///
+    /// `From<Vec<T>>` sizes the arena to exactly `vec.len()`. A vector that is
+    /// expected to see many `set`/`push` calls can pre-allocate arena room
+    /// here and avoid growing the arena chunk by chunk.
+    ///
+    /// # Panics
+    /// Panics with "capacity overflow" if `vec.len() + arena_extra` overflows
+    /// `usize` or the arena's first chunk would exceed `isize::MAX` bytes, as
+    /// `Vec::with_capacity` does.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from_vec_with_capacity(vec![1, 2, 3], 100);
+    /// for i in 0..100 {
+    ///     vec.set(0, i);
+    /// }
+    /// assert_eq!(vec.to_vec(), vec![99, 2, 3]);
+    /// ```
+    pub fn from_vec_with_capacity(vec: Vec<T>, arena_extra: usize) -> Self {
+        let capacity = vec
+            .len()
+            .checked_add(arena_extra)
+            .expect("capacity overflow");
+        let arena = Arc::new(CowArena::with_capacity(capacity));
+        let items = arena.alloc_extend(vec);
+        Self {
+            arena,
+            items: Arc::new(items),
+        }
+    }
+
+    /// Creates a new empty `CowVec` that shares the arena of `other`.
+    ///
+    /// Elements pushed into the new vector are allocated in the shared arena,
+    /// which lets pointer-only operations between the two vectors apply without
+    /// going through `clone`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let base = CowVec::from(vec![1, 2, 3]);
+    /// let mut related = CowVec::in_arena_of(&base);
+    /// related.push(4);
+    /// assert!(related.shares_arena_with(&base));
+    /// assert_eq!(base.len(), 3);
+    /// ```
+    pub fn in_arena_of(other: &CowVec<T>) -> Self {
+        Self {
+            arena: Arc::clone(&other.arena),
+            items: Arc::new(Vec::new()),
+        }
+    }
+
     /// Returns the number of elements in this vector.
     pub fn len(&self) -> usize {
         self.items.len()
@@ -126,6 +524,15 @@ impl<T> CowVec<T> {
         Arc::strong_count(&self.items) > 1
     }
 
+    /// Ensures this instance owns its pointer vector, copying it now if it is shared.
+    ///
+    /// Clones share the pointer vector until the first mutation. Calling this
+    /// up front moves that one-time O(n) copy out of a later, possibly
+    /// latency-sensitive, mutation. Does nothing if the structure is not shared.
+    pub fn make_structure_unique(&mut self) {
+        self.items_mut();
+    }
+
     /// Returns `true` if the storage (arena with actual values) is shared with other clones.
     ///
     /// This typically returns `true` after any clone operation, as all clones share
@@ -134,6 +541,113 @@ impl<T> CowVec<T> {
         Arc::strong_count(&self.arena) > 1
     }
 
+    /// Returns `true` if every value in the arena is a live element of this vector.
+    ///
+    /// This becomes `false` once values are left behind in the arena, e.g. by
+    /// `pop`, `remove`, `truncate`, or `set`. When the arena is shared, values
+    /// allocated by other clones also count against compactness, so this is
+    /// only a reliable measure of this vector's own garbage when
+    /// [`is_storage_shared`](CowVec::is_storage_shared) is `false`.
+    ///
+    /// Use [`deep_clone`](CowVec::deep_clone) or `clone_with_max_capacity` to
+    /// obtain a compact copy.
+    pub fn is_compact(&self) -> bool {
+        self.arena.len() == self.len()
+    }
+
+    /// Returns live, allocated, and garbage counts for this vector's arena in one call.
+    ///
+    /// The same caveat as [`is_compact`](CowVec::is_compact) applies: when the
+    /// arena is shared, `garbage` also includes values allocated by other clones.
+    pub fn arena_stats(&self) -> ArenaStats {
+        let live = self.len();
+        let allocated = self.arena.len();
+        ArenaStats {
+            live,
+            allocated,
+            garbage: allocated.saturating_sub(live),
+            arena_shared: self.is_storage_shared(),
+        }
+    }
+
+    /// Returns `true` if this vector and `other` store their values in the same arena.
+    ///
+    /// Clones and vectors produced by `split_off` share their source's arena;
+    /// independently constructed vectors never do.
+    pub fn shares_arena_with(&self, other: &CowVec<T>) -> bool {
+        Arc::ptr_eq(&self.arena, &other.arena)
+    }
+
+    /// Returns `true` if the slot at `index` in this vector and the slot at
+    /// `other_index` in `other` point to the same arena value.
+    ///
+    /// This compares element identity, not equality: after `set`, a clone holds
+    /// a new pointer even if the new value compares equal to the old one.
+    /// Returns `false` if either index is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec1 = CowVec::from(vec![1, 2, 3]);
+    /// let mut vec2 = vec1.clone();
+    /// vec2.set(1, 2);
+    /// assert!(vec1.ptr_eq_at(0, &vec2, 0));
+    /// assert!(!vec1.ptr_eq_at(1, &vec2, 1));
+    /// ```
+    pub fn ptr_eq_at(&self, index: usize, other: &CowVec<T>, other_index: usize) -> bool {
+        match (self.items.get(index), other.items.get(other_index)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Returns the indices at which this vector and `other` hold different element pointers.
+    ///
+    /// Elements are compared by identity only, without dereferencing: slots left
+    /// untouched since the two vectors diverged keep identical pointers, so this
+    /// is O(n) pointer comparisons. If the lengths differ, every index past the
+    /// shorter length is reported as well.
+    ///
+    /// Vectors that do not share an arena (see [`shares_arena_with`]) have no
+    /// pointers in common, so every index is reported.
+    ///
+    /// [`shares_arena_with`]: CowVec::shares_arena_with
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let base = CowVec::from(vec![1, 2, 3, 4]);
+    /// let mut edited = base.clone();
+    /// edited.set(1, 20);
+    /// edited.push(5);
+    /// assert_eq!(base.diff(&edited), vec![1, 4]);
+    /// ```
+    pub fn diff(&self, other: &CowVec<T>) -> Vec<usize> {
+        let common = self.len().min(other.len());
+        let longest = self.len().max(other.len());
+        self.items
+            .iter()
+            .zip(other.items.iter())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(index, _)| index)
+            .chain(common..longest)
+            .collect()
+    }
+
+    /// Returns an approximate number of bytes held by this vector.
+    ///
+    /// The estimate is the pointer vector's capacity times the pointer size, plus
+    /// the arena's allocation count times `size_of::<T>()`. It is a lower bound:
+    /// arena chunk overhead and heap memory owned by the elements themselves are
+    /// not counted. Since the arena may be shared, clones report overlapping usage.
+    pub fn memory_usage(&self) -> usize {
+        self.items.capacity() * std::mem::size_of::<NonNull<T>>()
+            + self.arena.len() * std::mem::size_of::<T>()
+    }
+
     /// Returns the elements as a slice of references.
     ///
     /// This provides efficient access to all elements without iteration,
@@ -150,7 +664,7 @@ impl<T> CowVec<T> {
     /// ```
     pub fn as_slice(&self) -> &[&T] {
         // SAFETY: This transmute is sound because:
-        // 1. `*const T` and `&T` have identical memory layouts (both are pointers)
+        // 1. `NonNull<T>` and `&T` have identical memory layouts (both are non-null pointers)
         // 2. All pointers in `self.items` are valid for the arena's lifetime
         // 3. The arena outlives this `CowVec` (guaranteed by Arc)
         // 4. The returned slice borrows `&self`, so it cannot outlive the CowVec
@@ -158,6 +672,104 @@ impl<T> CowVec<T> {
         unsafe { std::mem::transmute(self.items.as_slice()) }
     }
 
+    /// Returns a raw pointer to the start of the pointer vector.
+    ///
+    /// Mirrors `Vec::as_ptr`: the result points at `len()` consecutive
+    /// `*const T`, each pointing at an element in the arena.
+    ///
+    /// # Validity
+    /// - The pointer table is valid only until the next mutation of this
+    ///   vector, which may reallocate it or (if shared) copy it elsewhere.
+    /// - The element pointers themselves stay valid for as long as the arena
+    ///   lives, i.e. while any `CowVec` or [`ElemHandle`] sharing it exists.
+    /// - Elements must never be written through these pointers: they may be
+    ///   shared with other clones.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3]);
+    /// let first = unsafe { *vec.as_ptr() };
+    /// assert!(std::ptr::eq(first, vec.get(0).unwrap()));
+    /// ```
+    pub fn as_ptr(&self) -> *const *const T {
+        self.items.as_ptr().cast()
+    }
+
+    /// Returns the table of raw element pointers.
+    ///
+    /// The slice borrows this vector, so it cannot outlive a mutation. The
+    /// element pointers follow the validity rules described on
+    /// [`as_ptr`](CowVec::as_ptr) and must not be written through.
+    pub fn as_ptr_slice(&self) -> &[*const T] {
+        // SAFETY: `NonNull<T>` is `repr(transparent)` over `*const T`, so the
+        // pointer vector can be reinterpreted as a slice of raw pointers.
+        unsafe { std::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Returns an iterator over the raw element pointers, in order.
+    ///
+    /// Two arena-sharing vectors yield equal pointers exactly where they still
+    /// share a slot, so this is a building block for structural-sharing
+    /// analyses such as computing a patch between versions (see also
+    /// [`ptr_eq_at`](CowVec::ptr_eq_at) and [`diff`](CowVec::diff)). The
+    /// pointers follow the rules described on [`as_ptr`](CowVec::as_ptr).
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let base = CowVec::from(vec![1, 2, 3]);
+    /// let mut edited = base.clone();
+    /// edited.set(1, 20);
+    /// let changed = base.iter_ptr().zip(edited.iter_ptr()).filter(|(a, b)| a != b).count();
+    /// assert_eq!(changed, 1);
+    /// ```
+    pub fn iter_ptr(&self) -> impl Iterator<Item = *const T> + '_ {
+        self.items.iter().map(|ptr| ptr.as_ptr().cast_const())
+    }
+
+    /// Returns an iterator over `(index, pointer)` pairs, in order.
+    ///
+    /// Like [`iter_ptr`](CowVec::iter_ptr) with positions attached, which lets
+    /// tooling map arena slots back to logical indices across several
+    /// arena-sharing vectors.
+    pub fn enumerate_ptr(&self) -> impl Iterator<Item = (usize, *const T)> + '_ {
+        self.iter_ptr().enumerate()
+    }
+
+    /// Consumes the vector and returns a `'static` slice of references to its
+    /// elements, analogous to `Vec::leak`.
+    ///
+    /// This intentionally leaks memory: the arena (including any values no
+    /// longer reachable from this vector) and the pointer vector are never
+    /// freed. It is meant for data that lives for the rest of the program,
+    /// such as configuration loaded once at startup.
+    ///
+    /// If the pointer vector is shared with clones, it is copied first.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let names: &'static [&'static String] =
+    ///     CowVec::from(vec!["a".to_string(), "b".to_string()]).leak();
+    /// assert_eq!(names[1], "b");
+    /// ```
+    pub fn leak(self) -> &'static [&'static T]
+    where
+        T: 'static,
+    {
+        let CowVec { arena, items } = self;
+        std::mem::forget(arena);
+        let items = Arc::try_unwrap(items).unwrap_or_else(|shared| (*shared).clone());
+        // SAFETY: The arena was forgotten above, so its values are never
+        // dropped and every pointer stays valid for `'static`. `NonNull<T>`
+        // and `&T` have identical layouts, as in `as_slice()`.
+        unsafe { std::mem::transmute::<&'static [NonNull<T>], &'static [&'static T]>(items.leak()) }
+    }
+
     /// Returns a reference to the element at the given index, or `None` if out of bounds.
     pub fn get(&self, index: usize) -> Option<&T> {
         self.items.get(index).map(|ptr| {
@@ -165,7 +777,29 @@ impl<T> CowVec<T> {
             // 1. It was obtained from arena.alloc()
             // 2. The arena never moves or deallocates items
             // 3. The arena lives as long as this CowVec (via Arc)
-            unsafe { &**ptr }
+            unsafe { ptr.as_ref() }
+        })
+    }
+
+    /// Returns a reference to the element at the given index, without bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be less than `len()`. Calling this with an out-of-bounds
+    /// index is undefined behavior.
+    pub unsafe fn get_unchecked(&self, index: usize) -> &T {
+        // SAFETY: The caller guarantees index < len(); the pointer is then
+        // valid for the arena's lifetime as in get().
+        unsafe { self.items.get_unchecked(index).as_ref() }
+    }
+
+    /// Returns a stable handle to the element at the given index, or `None` if out of bounds.
+    ///
+    /// Unlike an index, the handle keeps referring to the same value even after
+    /// this vector is mutated (see [`ElemHandle`]).
+    pub fn handle(&self, index: usize) -> Option<ElemHandle<T>> {
+        self.items.get(index).map(|&ptr| ElemHandle {
+            arena: Arc::clone(&self.arena),
+            ptr,
         })
     }
 
@@ -178,26 +812,438 @@ impl<T> CowVec<T> {
         self.items_mut().push(ptr);
     }
 
-    /// Returns an iterator over references to the elements.
-    pub fn iter(&self) -> CowVecIter<'_, T> {
-        CowVecIter {
-            vec: self,
-            position: 0,
+    /// Appends the element at `index` in `other` by sharing its arena slot.
+    ///
+    /// Both vectors must share the same arena, so the pointer is copied and
+    /// the value is not cloned: afterwards both vectors reference the same
+    /// slot. As with clones, a later `set` on either side allocates a new slot
+    /// and leaves the other untouched.
+    ///
+    /// # Panics
+    /// Panics if the vectors do not share an arena, or if `index` is out of
+    /// bounds for `other`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let source = CowVec::from(vec!["a", "b", "c"]);
+    /// let mut picked = CowVec::in_arena_of(&source);
+    /// picked.push_from(&source, 2);
+    /// picked.push_from(&source, 0);
+    /// assert_eq!(picked.to_vec(), vec!["c", "a"]);
+    /// assert!(picked.ptr_eq_at(0, &source, 2));
+    /// ```
+    pub fn push_from(&mut self, other: &CowVec<T>, index: usize) {
+        assert!(
+            self.shares_arena_with(other),
+            "push_from requires both vectors to share an arena"
+        );
+        if index >= other.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                other.len(),
+                index
+            );
         }
+        let ptr = other.items[index];
+        self.items_mut().push(ptr);
     }
 
-    /// Returns a reference to the first element, or `None` if empty.
-    pub fn first(&self) -> Option<&T> {
-        self.get(0)
-    }
-
-    /// Returns a reference to the last element, or `None` if empty.
-    pub fn last(&self) -> Option<&T> {
-        if self.is_empty() {
-            None
-        } else {
-            self.get(self.len() - 1)
-        }
+    /// Tries to reserve room for at least `additional` more elements in both
+    /// the pointer vector and the arena.
+    ///
+    /// Returns an error instead of aborting if the capacity overflows or the
+    /// allocator reports a failure, so memory-constrained callers can react to
+    /// out-of-memory conditions. The pointer vector is reserved first; if the
+    /// arena then fails, the pointer vector keeps its extra capacity but the
+    /// contents are unchanged.
+    ///
+    /// The arena reserves one contiguous block for `additional` values. If the
+    /// current arena chunk lacks that much room, its remaining space is left
+    /// unused.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// assert!(vec.try_reserve(100).is_ok());
+    /// assert!(vec.try_reserve(usize::MAX).is_err());
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.items_mut().try_reserve(additional)?;
+        self.arena.try_reserve(additional)
+    }
+
+    /// Returns the capacity of this instance's pointer vector.
+    ///
+    /// This is independent of the arena, which grows in its own chunks.
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Reserves capacity for exactly `additional` more elements in the pointer vector.
+    ///
+    /// Forwards to `Vec::reserve_exact`; the arena is not affected.
+    ///
+    /// # Panics
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.items_mut().reserve_exact(additional);
+    }
+
+    /// Shrinks the pointer vector's capacity with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length and
+    /// `min_capacity`. Useful for long-lived vectors that have shrunk; the
+    /// arena is not affected (see `clone_with_max_capacity` for that).
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from((0..100).collect::<Vec<i32>>());
+    /// vec.truncate(3);
+    /// vec.shrink_to(10);
+    /// assert!(vec.capacity() >= 10 && vec.capacity() < 100);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.items_mut().shrink_to(min_capacity);
+    }
+
+    /// Appends an element to the back of this vector, returning an error
+    /// instead of aborting if either the pointer vector or the arena cannot
+    /// grow.
+    ///
+    /// Room is reserved in both through [`try_reserve`](CowVec::try_reserve)
+    /// before `value` is allocated. On error, `value` is dropped and the
+    /// contents are unchanged.
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        self.try_reserve(1)?;
+        self.push(value);
+        Ok(())
+    }
+
+    /// Returns an iterator over references to the elements.
+    pub fn iter(&self) -> CowVecIter<'_, T> {
+        CowVecIter {
+            vec: self,
+            position: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Returns an iterator over references to the elements in reverse order.
+    ///
+    /// Shorthand for `iter().rev()`; the result is exact-size.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3]);
+    /// let reversed: Vec<i32> = vec.rev_iter().copied().collect();
+    /// assert_eq!(reversed, vec![3, 2, 1]);
+    /// ```
+    pub fn rev_iter(&self) -> std::iter::Rev<CowVecIter<'_, T>> {
+        self.iter().rev()
+    }
+
+    /// Returns an iterator over references to the elements, starting at `start`.
+    ///
+    /// Equivalent to `iter().skip(start)`, but the offset is applied up front
+    /// and the result is still a `CowVecIter` with an exact `size_hint`. A
+    /// `start` past the end is clamped to `len()`, yielding an empty iterator.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let page: Vec<i32> = vec.iter_from(2).copied().collect();
+    /// assert_eq!(page, vec![3, 4, 5]);
+    /// ```
+    pub fn iter_from(&self, start: usize) -> CowVecIter<'_, T> {
+        CowVecIter {
+            vec: self,
+            position: start.min(self.len()),
+            end: self.len(),
+        }
+    }
+
+    /// Returns an iterator over all overlapping windows of `N` elements, as arrays.
+    ///
+    /// Yields nothing if the vector is shorter than `N`.
+    ///
+    /// # Panics
+    /// Panics if `N` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4]);
+    /// let sums: Vec<i32> = vec.array_windows::<2>().map(|[a, b]| a + b).collect();
+    /// assert_eq!(sums, vec![3, 5, 7]);
+    /// ```
+    pub fn array_windows<const N: usize>(&self) -> impl Iterator<Item = [&T; N]> {
+        self.as_slice()
+            .windows(N)
+            .map(|window| <[&T; N]>::try_from(window).unwrap())
+    }
+
+    /// Calls `f` on each overlapping window of `size` elements, front to back.
+    ///
+    /// A callback-style alternative to iterating `as_slice().windows(size)`.
+    /// Nothing is called if the vector is shorter than `size`.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4]);
+    /// let mut sums = Vec::new();
+    /// vec.for_each_window(2, |w| sums.push(w[0] + w[1]));
+    /// assert_eq!(sums, vec![3, 5, 7]);
+    /// ```
+    pub fn for_each_window<F>(&self, size: usize, mut f: F)
+    where
+        F: FnMut(&[&T]),
+    {
+        assert!(size != 0, "window size must be non-zero");
+        for window in self.as_slice().windows(size) {
+            f(window);
+        }
+    }
+
+    /// Returns an iterator over non-overlapping chunks of `N` elements, as arrays.
+    ///
+    /// If the length is not a multiple of `N`, the trailing elements are skipped.
+    ///
+    /// # Panics
+    /// Panics if `N` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let pairs: Vec<[&i32; 2]> = vec.array_chunks::<2>().collect();
+    /// assert_eq!(pairs, vec![[&1, &2], [&3, &4]]);
+    /// ```
+    pub fn array_chunks<const N: usize>(&self) -> impl Iterator<Item = [&T; N]> {
+        self.as_slice()
+            .chunks_exact(N)
+            .map(|chunk| <[&T; N]>::try_from(chunk).unwrap())
+    }
+
+    /// Returns an iterator over chunks of `n` elements, starting at the end.
+    ///
+    /// Matches `slice::rchunks`: the last chunk yielded holds the leftover
+    /// elements from the front if the length is not a multiple of `n`.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let mut chunks = vec.rchunks(2);
+    /// assert_eq!(chunks.next(), Some(&[&4, &5][..]));
+    /// assert_eq!(chunks.next(), Some(&[&2, &3][..]));
+    /// assert_eq!(chunks.next(), Some(&[&1][..]));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    pub fn rchunks(&self, n: usize) -> std::slice::RChunks<'_, &T> {
+        self.as_slice().rchunks(n)
+    }
+
+    /// Returns an iterator over chunks of exactly `n` elements, starting at the end.
+    ///
+    /// Matches `slice::rchunks_exact`: leftover elements at the front are
+    /// skipped and available via `remainder()` on the returned iterator.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    pub fn rchunks_exact(&self, n: usize) -> std::slice::RChunksExact<'_, &T> {
+        self.as_slice().rchunks_exact(n)
+    }
+
+    /// Returns an iterator over runs of consecutive elements, split wherever
+    /// `pred` returns `false` for an adjacent pair.
+    ///
+    /// Matches `slice::chunk_by`: each run is a slice of references in which
+    /// every adjacent pair satisfies `pred`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 1, 2, 3, 3, 3]);
+    /// let lens: Vec<usize> = vec.chunk_by(|a, b| a == b).map(|run| run.len()).collect();
+    /// assert_eq!(lens, vec![2, 1, 3]);
+    /// ```
+    pub fn chunk_by<F>(&self, pred: F) -> ChunkBy<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        ChunkBy {
+            slice: self.as_slice(),
+            predicate: pred,
+        }
+    }
+
+    /// Returns an iterator over segments separated by elements matching `pred`.
+    ///
+    /// Matches `slice::split`: the matched elements are not included in any
+    /// segment, and leading, trailing, or adjacent matches produce empty
+    /// segments.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 0, 2, 0, 3]);
+    /// let segments: Vec<Vec<i32>> = vec
+    ///     .split(|&x| x == 0)
+    ///     .map(|seg| seg.iter().map(|x| **x).collect())
+    ///     .collect();
+    /// assert_eq!(segments, vec![vec![1], vec![2], vec![3]]);
+    /// ```
+    pub fn split<F>(&self, pred: F) -> Split<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Split {
+            slice: Some(self.as_slice()),
+            predicate: pred,
+        }
+    }
+
+    /// Returns an iterator over segments separated by elements matching
+    /// `pred`, starting from the back.
+    ///
+    /// Matches `slice::rsplit`: yields the same segments as
+    /// [`split`](CowVec::split), in reverse order.
+    pub fn rsplit<F>(&self, pred: F) -> RSplit<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        RSplit {
+            slice: Some(self.as_slice()),
+            predicate: pred,
+        }
+    }
+
+    /// Returns an iterator over at most `n` segments separated by elements matching `pred`.
+    ///
+    /// Matches `slice::splitn`: the matched elements are not included in any
+    /// segment, and the last segment contains the remainder of the vector.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 0, 2, 0, 3]);
+    /// let lens: Vec<usize> = vec.splitn(2, |&x| x == 0).map(|seg| seg.len()).collect();
+    /// assert_eq!(lens, vec![1, 3]);
+    /// ```
+    pub fn splitn<F>(&self, n: usize, pred: F) -> SplitN<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        SplitN {
+            slice: Some(self.as_slice()),
+            remaining: n,
+            predicate: pred,
+        }
+    }
+
+    /// Returns an iterator over at most `n` segments separated by elements
+    /// matching `pred`, starting from the back.
+    ///
+    /// Matches `slice::rsplitn`: the matched elements are not included in any
+    /// segment, and the last segment contains the remainder of the vector.
+    pub fn rsplitn<F>(&self, n: usize, pred: F) -> RSplitN<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        RSplitN {
+            slice: Some(self.as_slice()),
+            remaining: n,
+            predicate: pred,
+        }
+    }
+
+    /// Returns an iterator over pairs of elements from this vector and `other`.
+    ///
+    /// Stops at the end of the shorter vector.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let keys = CowVec::from(vec!["a", "b"]);
+    /// let values = CowVec::from(vec![1, 2, 3]);
+    /// let pairs: Vec<_> = keys.zip(&values).collect();
+    /// assert_eq!(pairs, vec![(&"a", &1), (&"b", &2)]);
+    /// ```
+    pub fn zip<'a, U>(&'a self, other: &'a CowVec<U>) -> impl Iterator<Item = (&'a T, &'a U)> {
+        self.iter().zip(other.iter())
+    }
+
+    /// Returns a reference to the first element, or `None` if empty.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the last element, or `None` if empty.
+    pub fn last(&self) -> Option<&T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.get(self.len() - 1)
+        }
+    }
+
+    /// Returns the first `N` elements as an array, or `None` if the vector is shorter than `N`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3]);
+    /// assert_eq!(vec.first_chunk::<2>(), Some([&1, &2]));
+    /// assert_eq!(vec.first_chunk::<4>(), None);
+    /// ```
+    pub fn first_chunk<const N: usize>(&self) -> Option<[&T; N]> {
+        let slice = self.as_slice();
+        slice
+            .get(..N)
+            .map(|chunk| <[&T; N]>::try_from(chunk).unwrap())
+    }
+
+    /// Returns the last `N` elements as an array, or `None` if the vector is shorter than `N`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3]);
+    /// assert_eq!(vec.last_chunk::<2>(), Some([&2, &3]));
+    /// assert_eq!(vec.last_chunk::<4>(), None);
+    /// ```
+    pub fn last_chunk<const N: usize>(&self) -> Option<[&T; N]> {
+        let slice = self.as_slice();
+        let start = slice.len().checked_sub(N)?;
+        Some(<[&T; N]>::try_from(&slice[start..]).unwrap())
     }
 
     /// Removes the last element and returns it, or `None` if empty.
@@ -207,7 +1253,7 @@ impl<T> CowVec<T> {
     pub fn pop(&mut self) -> Option<&T> {
         self.items_mut().pop().map(|ptr| {
             // SAFETY: Same as get() - pointer is valid for arena's lifetime
-            unsafe { &*ptr }
+            unsafe { ptr.as_ref() }
         })
     }
 
@@ -223,7 +1269,7 @@ impl<T> CowVec<T> {
     pub fn remove(&mut self, index: usize) -> &T {
         let ptr = self.items_mut().remove(index);
         // SAFETY: Same as get() - pointer is valid for arena's lifetime
-        unsafe { &*ptr }
+        unsafe { ptr.as_ref() }
     }
 
     /// Swaps two elements in the vector.
@@ -234,6 +1280,98 @@ impl<T> CowVec<T> {
         self.items_mut().swap(a, b);
     }
 
+    /// Swaps the element at `self_idx` with the element at `other_idx` in `other`.
+    ///
+    /// Both vectors must share the same arena (see
+    /// [`shares_arena_with`](CowVec::shares_arena_with)), so only the two
+    /// pointers are exchanged and no value is cloned. Copy-on-write applies to
+    /// each vector's pointer vector as usual.
+    ///
+    /// # Panics
+    /// Panics if the vectors do not share an arena, or if either index is out
+    /// of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut left = CowVec::from(vec![1, 2, 3, 4]);
+    /// let mut right = left.split_off(2);
+    /// left.swap_with(0, &mut right, 1);
+    /// assert_eq!(left.to_vec(), vec![4, 2]);
+    /// assert_eq!(right.to_vec(), vec![3, 1]);
+    /// ```
+    pub fn swap_with(&mut self, self_idx: usize, other: &mut CowVec<T>, other_idx: usize) {
+        assert!(
+            self.shares_arena_with(other),
+            "swap_with requires both vectors to share an arena"
+        );
+        for (index, len) in [(self_idx, self.len()), (other_idx, other.len())] {
+            if index >= len {
+                panic!(
+                    "index out of bounds: the len is {} but the index is {}",
+                    len, index
+                );
+            }
+        }
+        std::mem::swap(
+            &mut self.items_mut()[self_idx],
+            &mut other.items_mut()[other_idx],
+        );
+    }
+
+    /// Copies the elements in `src` to the range starting at `dest`, overwriting them.
+    ///
+    /// Matches `slice::copy_within`, including for overlapping ranges. Only
+    /// pointers are copied, so afterwards both ranges refer to the same arena
+    /// values and no allocation takes place.
+    ///
+    /// # Panics
+    /// Panics if `src` is out of bounds or if `dest + src.len()` exceeds `len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// vec.copy_within(1..3, 3);
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3, 2, 3]);
+    /// ```
+    pub fn copy_within<R: RangeBounds<usize>>(&mut self, src: R, dest: usize) {
+        let (start, end) = self.resolve_range(src);
+        self.items_mut().copy_within(start..end, dest);
+    }
+
+    /// Rotates the vector in place by `mid` positions, in either direction.
+    ///
+    /// A positive `mid` rotates left, like `slice::rotate_left`: the element
+    /// at index `mid` becomes the first. A negative `mid` rotates right by
+    /// `-mid`, like `slice::rotate_right`. Offsets wrap modulo `len()`, so any
+    /// value is accepted; rotating an empty vector does nothing. Only pointers
+    /// are moved.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// vec.rotate(2);
+    /// assert_eq!(vec.to_vec(), vec![3, 4, 5, 1, 2]);
+    /// vec.rotate(-2);
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn rotate(&mut self, mid: isize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        // `len` fits in `isize` because a `Vec` never exceeds `isize::MAX` bytes.
+        let shift = mid.rem_euclid(len as isize) as usize;
+        if shift != 0 {
+            self.items_mut().rotate_left(shift);
+        }
+    }
+
     /// Reverses the order of elements in the vector.
     pub fn reverse(&mut self) {
         self.items_mut().reverse();
@@ -256,194 +1394,1794 @@ impl<T> CowVec<T> {
         self.items_mut().clear();
     }
 
-    /// Extends the vector with elements from an iterator.
-    pub fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            self.push(item);
+    /// Clears the vector while keeping the pointer vector's allocation.
+    ///
+    /// `clear()` already keeps capacity when this instance owns its pointer
+    /// vector; this method names that intent and avoids copying a shared
+    /// pointer vector just to empty it (a shared vector has no allocation of
+    /// its own to keep, so it is simply detached).
+    ///
+    /// Values remain in the arena; see [`reset`](CowVec::reset) to drop them.
+    pub fn clear_keep_capacity(&mut self) {
+        match Arc::get_mut(&mut self.items) {
+            Some(items) => items.clear(),
+            None => self.items = Arc::new(Vec::new()),
         }
     }
 
-    /// Returns the index of the first element matching the predicate.
-    pub fn position<P>(&self, predicate: P) -> Option<usize>
-    where
-        P: FnMut(&T) -> bool,
-    {
-        self.iter().position(predicate)
+    /// Clears the vector and detaches it from its arena, giving it a fresh
+    /// private one.
+    ///
+    /// If this vector was the arena's sole owner, every value in it (live or
+    /// dead) is dropped immediately. If the arena is shared with clones or
+    /// [`ElemHandle`]s, nothing can be freed yet; the old arena lives on until
+    /// its last owner is dropped, but this vector no longer keeps it alive.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// vec.set(0, 10);
+    /// vec.reset();
+    /// assert!(vec.is_empty());
+    /// assert_eq!(vec.memory_usage(), vec.capacity() * std::mem::size_of::<usize>());
+    /// ```
+    pub fn reset(&mut self) {
+        self.clear_keep_capacity();
+        self.arena = Arc::new(CowArena::new());
+    }
+
+    /// Extends the vector with elements from an iterator.
+    ///
+    /// All elements are allocated in the arena as a single batch.
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        // Collect first so the caller's iterator never runs under the arena lock.
+        let values: Vec<T> = iter.into_iter().collect();
+        let ptrs = self.arena.alloc_extend(values);
+        self.items_mut().extend(ptrs);
+    }
+
+    /// Moves all elements of `other` to the end of this vector.
+    ///
+    /// Values are moved into the arena in a single batch, without cloning;
+    /// `other` is consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2]);
+    /// vec.append_vec(vec![3, 4]);
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn append_vec(&mut self, other: Vec<T>) {
+        let ptrs = self.arena.alloc_extend(other);
+        self.items_mut().extend(ptrs);
+    }
+
+    /// Returns the index of the first element matching the predicate.
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    /// Returns `true` if the vector contains an element equal to `value`,
+    /// comparing through `Borrow`.
+    ///
+    /// Unlike [`contains`](CowVec::contains), the query does not have to be a
+    /// `&T`, so a `CowVec<String>` can be searched with a `&str`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec!["foo".to_string(), "bar".to_string()]);
+    /// assert!(vec.contains_by_borrow("foo"));
+    /// assert!(!vec.contains_by_borrow("baz"));
+    /// ```
+    pub fn contains_by_borrow<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: PartialEq + ?Sized,
+    {
+        self.iter().any(|item| item.borrow() == value)
+    }
+
+    /// Applies `f` to each element and collects the results into a new
+    /// `CowVec` backed by a fresh arena.
+    ///
+    /// The new arena is sized to `len()`, and the mapped values are allocated
+    /// in a single batch.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3]);
+    /// let strings: CowVec<String> = vec.map(|x| x.to_string());
+    /// assert_eq!(strings, vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> CowVec<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        // Collect first so `f` never runs under the arena lock.
+        let values: Vec<U> = self.iter().map(f).collect();
+        let arena = Arc::new(CowArena::with_capacity(values.len()));
+        let items = arena.alloc_extend(values);
+        CowVec {
+            arena,
+            items: Arc::new(items),
+        }
+    }
+
+    /// Replaces each element with `f(&old)`, keeping the same arena.
+    ///
+    /// Unlike [`map`](CowVec::map), this is copy-on-write: every new value is
+    /// allocated in the shared arena (one slot per element, in a single batch)
+    /// and only this instance's pointers are rewired, so clones keep seeing the
+    /// original values.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// let original = vec.clone();
+    /// vec.map_in_place(|x| x * 2);
+    /// assert_eq!(vec.to_vec(), vec![2, 4, 6]);
+    /// assert_eq!(original.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn map_in_place<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> T,
+    {
+        // Collect first so `f` never runs under the arena lock.
+        let values: Vec<T> = self.iter().map(f).collect();
+        let ptrs = self.arena.alloc_extend(values);
+        // Replacing the whole pointer vector avoids copying a shared one first.
+        self.items = Arc::new(ptrs);
+    }
+
+    /// Applies `f` to each element in order and returns the first `Some` result.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec!["a", "12", "b", "34"]);
+    /// assert_eq!(vec.find_map(|s| s.parse::<i32>().ok()), Some(12));
+    /// ```
+    pub fn find_map<B, F>(&self, f: F) -> Option<B>
+    where
+        F: FnMut(&T) -> Option<B>,
+    {
+        self.iter().find_map(f)
+    }
+
+    /// Applies `f` to each element and collects the `Some` results into a `Vec`.
+    ///
+    /// The mapped values are not stored in the arena, so the result is an owned
+    /// `Vec` rather than a `CowVec`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec!["1", "x", "2", "3"]);
+    /// assert_eq!(vec.filter_map(|s| s.parse::<i32>().ok()), vec![1, 2, 3]);
+    /// ```
+    pub fn filter_map<B, F>(&self, f: F) -> Vec<B>
+    where
+        F: FnMut(&T) -> Option<B>,
+    {
+        self.iter().filter_map(f).collect()
+    }
+
+    /// Folds every element into an accumulator, front to back.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4]);
+    /// assert_eq!(vec.fold(0, |sum, x| sum + x), 10);
+    /// ```
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        self.iter().fold(init, f)
+    }
+
+    /// Returns the number of elements matching the predicate.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from((1..7).collect::<Vec<i32>>());
+    /// assert_eq!(vec.count(|&x| x % 2 == 0), 3);
+    /// ```
+    pub fn count<P>(&self, mut predicate: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().filter(|&item| predicate(item)).count()
+    }
+
+    /// Returns the index of the last element matching the predicate.
+    ///
+    /// Elements are scanned from the back toward the front.
+    pub fn rposition<P>(&self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.items.iter().rposition(|ptr| {
+            // SAFETY: Pointer is valid for arena's lifetime
+            predicate(unsafe { ptr.as_ref() })
+        })
+    }
+
+    /// Returns a reference to the last element matching the predicate.
+    ///
+    /// Elements are scanned from the back toward the front.
+    pub fn rfind<P>(&self, mut predicate: P) -> Option<&T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.items
+            .iter()
+            .rev()
+            .map(|ptr| {
+                // SAFETY: Pointer is valid for arena's lifetime
+                unsafe { ptr.as_ref() }
+            })
+            .find(|value| predicate(value))
+    }
+
+    /// Returns the index of the partition point according to the given predicate.
+    ///
+    /// The vector is assumed to be partitioned: all elements for which `pred`
+    /// returns `true` precede those for which it returns `false`. The result
+    /// is the index of the first element of the second group, found by binary
+    /// search. If the vector is not partitioned, the result is unspecified.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 3, 5, 6, 7]);
+    /// assert_eq!(vec.partition_point(|&x| x < 5), 4);
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.as_slice().partition_point(|item| pred(item))
+    }
+
+    /// Returns `true` if `compare` holds for every adjacent pair of elements.
+    ///
+    /// `compare(a, b)` should return `true` if `a` may precede `b`. Empty and
+    /// single-element vectors are always sorted.
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.as_slice()
+            .windows(2)
+            .all(|pair| compare(pair[0], pair[1]))
+    }
+
+    /// Returns `true` if the elements are sorted by the key extracted with `f`.
+    ///
+    /// Empty and single-element vectors are always sorted.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec!["a", "bb", "ccc"]);
+    /// assert!(vec.is_sorted_by_key(|s| s.len()));
+    /// ```
+    pub fn is_sorted_by_key<K, F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&T) -> K,
+        K: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| f(a) <= f(b))
+    }
+
+    /// Inserts an element at position `index`, shifting all elements after it to the right.
+    ///
+    /// # Panics
+    /// Panics if `index > len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// vec.insert(1, 10);
+    /// assert_eq!(vec.to_vec(), vec![1, 10, 2, 3]);
+    /// ```
+    pub fn insert(&mut self, index: usize, value: T) {
+        let ptr = self.arena.alloc(value);
+        self.items_mut().insert(index, ptr);
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// Removes all elements for which the predicate returns `false`.
+    ///
+    /// Note: Removed values remain in the shared arena.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// vec.retain(|&x| x % 2 == 0);
+    /// assert_eq!(vec.to_vec(), vec![2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.items_mut().retain(|ptr| {
+            // SAFETY: Pointer is valid for arena's lifetime
+            let value = unsafe { ptr.as_ref() };
+            f(value)
+        });
+    }
+
+    /// Retains only the elements specified by the predicate, which also
+    /// receives each element's index.
+    ///
+    /// The index is the element's position before any removals by this call.
+    ///
+    /// Note: Removed values remain in the shared arena.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec!['a', 'b', 'c', 'd', 'e']);
+    /// vec.retain_indexed(|i, _| i % 2 == 0);
+    /// assert_eq!(vec.to_vec(), vec!['a', 'c', 'e']);
+    /// ```
+    pub fn retain_indexed<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &T) -> bool,
+    {
+        let mut index = 0;
+        self.items_mut().retain(|ptr| {
+            // SAFETY: Pointer is valid for arena's lifetime
+            let value = unsafe { ptr.as_ref() };
+            let keep = f(index, value);
+            index += 1;
+            keep
+        });
+    }
+
+    /// Splits the vector into two at the given index.
+    ///
+    /// Returns a new `CowVec` containing elements from `at` to the end.
+    /// After this call, `self` contains elements `[0, at)` and the returned
+    /// `CowVec` contains elements `[at, len)`.
+    ///
+    /// Both vectors share the same arena, so this is an efficient operation.
+    ///
+    /// # Panics
+    /// Panics if `at > len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let tail = vec.split_off(3);
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    /// assert_eq!(tail.to_vec(), vec![4, 5]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let tail_items = self.items_mut().split_off(at);
+        Self {
+            arena: Arc::clone(&self.arena),
+            items: Arc::new(tail_items),
+        }
+    }
+
+    /// Splits the vector at `at` like [`split_off`](CowVec::split_off), or
+    /// returns `None` instead of panicking if `at > len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// assert!(vec.try_split_off(4).is_none());
+    /// let tail = vec.try_split_off(1).unwrap();
+    /// assert_eq!(tail.to_vec(), vec![2, 3]);
+    /// ```
+    pub fn try_split_off(&mut self, at: usize) -> Option<Self> {
+        if at > self.len() {
+            return None;
+        }
+        Some(self.split_off(at))
+    }
+
+    /// Moves all elements out of `self` into two new `CowVec`s covering
+    /// `[0, mid)` and `[mid, len)`, leaving `self` empty.
+    ///
+    /// Both halves share this vector's arena and can be mutated independently
+    /// with copy-on-write. Unlike `split_off`, the head is returned rather than
+    /// kept in `self`. If the pointer vector is shared with clones, it is copied;
+    /// otherwise it is reused for the head.
+    ///
+    /// # Panics
+    /// Panics if `mid > len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let (mut head, tail) = vec.split_at_mut(2);
+    /// head.set(0, 10);
+    /// assert_eq!(head.to_vec(), vec![10, 2]);
+    /// assert_eq!(tail.to_vec(), vec![3, 4, 5]);
+    /// assert!(vec.is_empty());
+    /// ```
+    pub fn split_at_mut(&mut self, mid: usize) -> (Self, Self) {
+        if mid > self.items.len() {
+            panic!(
+                "mid > len: the len is {} but mid is {}",
+                self.items.len(),
+                mid
+            );
+        }
+        let mut head = Arc::unwrap_or_clone(std::mem::take(&mut self.items));
+        let tail = head.split_off(mid);
+        (
+            Self {
+                arena: Arc::clone(&self.arena),
+                items: Arc::new(head),
+            },
+            Self {
+                arena: Arc::clone(&self.arena),
+                items: Arc::new(tail),
+            },
+        )
+    }
+
+    /// Returns a new `CowVec` containing the elements in the given range.
+    ///
+    /// The result shares this vector's arena, so only the pointers in the range
+    /// are copied; no elements are cloned. Mutating either vector afterwards
+    /// does not affect the other.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let sub = vec.subvec(1..3);
+    /// assert_eq!(sub.to_vec(), vec![2, 3]);
+    /// ```
+    pub fn subvec<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let (start, end) = self.resolve_range(range);
+        Self {
+            arena: Arc::clone(&self.arena),
+            items: Arc::new(self.items[start..end].to_vec()),
+        }
+    }
+
+    /// Splits the elements into two new `CowVec`s by a predicate.
+    ///
+    /// The first vector holds the elements for which `f` returns `true`, the
+    /// second the rest, each in their original order. Both share this vector's
+    /// arena; only pointers are copied, and no elements are cloned.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let (even, odd) = vec.partition(|&x| x % 2 == 0);
+    /// assert_eq!(even.to_vec(), vec![2, 4]);
+    /// assert_eq!(odd.to_vec(), vec![1, 3, 5]);
+    /// ```
+    pub fn partition<F>(&self, mut f: F) -> (Self, Self)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let (matching, rest): (Vec<NonNull<T>>, Vec<NonNull<T>>) =
+            self.items.iter().partition(|ptr| {
+                // SAFETY: Pointer is valid for arena's lifetime
+                f(unsafe { ptr.as_ref() })
+            });
+        (
+            Self {
+                arena: Arc::clone(&self.arena),
+                items: Arc::new(matching),
+            },
+            Self {
+                arena: Arc::clone(&self.arena),
+                items: Arc::new(rest),
+            },
+        )
+    }
+
+    /// Returns a new `CowVec` holding the leading elements that satisfy `pred`.
+    ///
+    /// Stops at the first element for which `pred` returns `false`. The result
+    /// shares this vector's arena; only pointers are copied.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3, 4]);
+    /// let head = vec.take_while(|&x| x < 3);
+    /// assert_eq!(head.to_vec(), vec![1, 2]);
+    /// assert!(head.shares_arena_with(&vec));
+    /// ```
+    pub fn take_while<P>(&self, pred: P) -> Self
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let end = self.leading_run(pred);
+        self.subvec(..end)
+    }
+
+    /// Returns a new `CowVec` without the leading elements that satisfy `pred`.
+    ///
+    /// The complement of [`take_while`](CowVec::take_while): the result starts
+    /// at the first element for which `pred` returns `false` and shares this
+    /// vector's arena.
+    pub fn skip_while<P>(&self, pred: P) -> Self
+    where
+        P: FnMut(&T) -> bool,
+    {
+        let start = self.leading_run(pred);
+        self.subvec(start..)
+    }
+
+    /// Returns the length of the leading run of elements satisfying `pred`.
+    fn leading_run<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().position(|x| !pred(x)).unwrap_or(self.len())
+    }
+
+    /// Removes the specified range and replaces it with elements from the iterator.
+    ///
+    /// Returns the removed elements as a `Vec` of references.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// let removed: Vec<&i32> = vec.splice(1..3, vec![10, 20, 30]);
+    /// assert_eq!(removed, vec![&2, &3]);
+    /// assert_eq!(vec.to_vec(), vec![1, 10, 20, 30, 4, 5]);
+    /// ```
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Vec<&T>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        let (start, end) = self.resolve_range(range);
+
+        // Allocate new elements in arena
+        let values: Vec<T> = replace_with.into_iter().collect();
+        let new_ptrs = self.arena.alloc_extend(values);
+
+        // Splice the pointer vector and collect removed pointers
+        let removed_ptrs: Vec<NonNull<T>> = self.items_mut().splice(start..end, new_ptrs).collect();
+
+        // Convert removed pointers to references
+        removed_ptrs
+            .into_iter()
+            .map(|ptr| {
+                // SAFETY: Pointer is valid for arena's lifetime
+                unsafe { ptr.as_ref() }
+            })
+            .collect()
+    }
+}
+
+impl<T: PartialEq> CowVec<T> {
+    /// Compares the elements of this vector against a slice, element by element.
+    fn eq_slice(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+
+    /// Compares every element by value, ignoring arena sharing.
+    ///
+    /// Unlike `==`, which treats slots holding the same arena pointer as equal
+    /// without comparing them, this always calls `T::eq` on each pair. The two
+    /// agree except for values not equal to themselves, such as `f64::NAN`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1.0, f64::NAN]);
+    /// let clone = vec.clone();
+    /// assert!(vec == clone);
+    /// assert!(!vec.values_eq(&clone));
+    /// ```
+    pub fn values_eq(&self, other: &CowVec<T>) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+    }
+
+    /// Returns `true` if the vector contains the given value.
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|item| item == value)
+    }
+
+    /// Returns the number of elements equal to `value`.
+    pub fn count_eq(&self, value: &T) -> usize {
+        self.iter().filter(|&item| item == value).count()
+    }
+
+    /// Removes the first element equal to `value` and returns a reference to it.
+    ///
+    /// Only the pointer is removed; the value stays in the arena. Returns
+    /// `None` (without touching the pointer vector) if no element matches.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 2]);
+    /// assert_eq!(vec.remove_item(&2), Some(&2));
+    /// assert_eq!(vec.to_vec(), vec![1, 3, 2]);
+    /// assert_eq!(vec.remove_item(&5), None);
+    /// ```
+    pub fn remove_item(&mut self, value: &T) -> Option<&T> {
+        let index = self.position(|item| item == value)?;
+        Some(self.remove(index))
+    }
+
+    /// Removes consecutive repeated elements and returns references to the
+    /// removed ones, in order.
+    ///
+    /// Only pointers are removed; the values linger in the arena, which is why
+    /// they can still be returned by reference.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 1, 2, 2, 2, 3]);
+    /// let removed = vec.dedup_returning();
+    /// assert_eq!(removed, vec![&1, &2, &2]);
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn dedup_returning(&mut self) -> Vec<&T> {
+        let mut removed = Vec::new();
+        self.items_mut().dedup_by(|current, previous| {
+            // SAFETY: Pointers are valid for arena's lifetime
+            let duplicate = unsafe { current.as_ref() == previous.as_ref() };
+            if duplicate {
+                removed.push(*current);
+            }
+            duplicate
+        });
+        removed
+            .into_iter()
+            // SAFETY: Same as get() - pointer is valid for arena's lifetime
+            .map(|ptr| unsafe { ptr.as_ref() })
+            .collect()
+    }
+
+    /// Returns the number of maximal runs of equal consecutive elements.
+    ///
+    /// Returns `0` for an empty vector and `1` if all elements are equal.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 1, 2, 2, 2, 3]);
+    /// assert_eq!(vec.count_runs(), 3);
+    /// ```
+    pub fn count_runs(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let slice = self.as_slice();
+        1 + slice.windows(2).filter(|pair| pair[0] != pair[1]).count()
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T> CowVec<T> {
+    /// Randomly permutes the elements in place.
+    ///
+    /// Uses a Fisher–Yates shuffle over the pointer vector; no elements are
+    /// moved or allocated.
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.items_mut().shuffle(rng);
+    }
+
+    /// Returns a reference to a uniformly chosen random element, or `None` if empty.
+    pub fn choose<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<&T> {
+        self.as_slice().choose(rng).copied()
+    }
+
+    /// Returns a new `CowVec` of `k` distinct elements chosen at random.
+    ///
+    /// The result shares this vector's arena; only pointers are copied. If
+    /// `k >= len()`, the result is a shuffled copy of all elements.
+    ///
+    /// "Distinct" refers to positions: each element of `self` is drawn at most once.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R, k: usize) -> Self {
+        let items: Vec<NonNull<T>> = if k >= self.len() {
+            let mut items = self.items.to_vec();
+            items.shuffle(rng);
+            items
+        } else {
+            index::sample(rng, self.len(), k)
+                .into_iter()
+                .map(|index| self.items[index])
+                .collect()
+        };
+        Self {
+            arena: Arc::clone(&self.arena),
+            items: Arc::new(items),
+        }
+    }
+}
+
+impl<T: PartialOrd> CowVec<T> {
+    /// Returns `true` if the elements are sorted in ascending order.
+    ///
+    /// Empty and single-element vectors are always sorted.
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+}
+
+impl<T: Ord> CowVec<T> {
+    /// Inserts `value` into a sorted vector, keeping it sorted, and returns
+    /// the index it was inserted at.
+    ///
+    /// The insertion point is found by binary search. If equal elements are
+    /// already present, `value` goes after all of them, so repeated inserts of
+    /// equal values keep their insertion order. The result is unspecified if
+    /// the vector is not sorted.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 3, 3, 7]);
+    /// assert_eq!(vec.insert_sorted(3), 3);
+    /// assert_eq!(vec.insert_sorted(5), 4);
+    /// assert_eq!(vec.to_vec(), vec![1, 3, 3, 3, 5, 7]);
+    /// ```
+    pub fn insert_sorted(&mut self, value: T) -> usize {
+        let index = self.partition_point(|item| item <= &value);
+        let ptr = self.arena.alloc(value);
+        self.items_mut().insert(index, ptr);
+        index
+    }
+
+    /// Sorts the vector with a stable sort.
+    ///
+    /// Only pointers are reordered; no element is moved or cloned. Elements
+    /// that compare equal keep their relative order, and therefore also their
+    /// arena slots in that order, so pointer-based comparisons such as
+    /// [`diff`](CowVec::diff) against a clone sorted the same way stay minimal.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![3, 1, 2]);
+    /// vec.sort_stable();
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn sort_stable(&mut self) {
+        self.items_mut().sort_by(|a, b| {
+            // SAFETY: Pointers are valid for arena's lifetime
+            unsafe { a.as_ref().cmp(b.as_ref()) }
+        });
+    }
+
+    /// Returns a reference to the minimum element, or `None` if empty.
+    ///
+    /// If several elements are equally minimal, the first one is returned.
+    pub fn min(&self) -> Option<&T> {
+        self.iter().min()
+    }
+
+    /// Returns a reference to the maximum element, or `None` if empty.
+    ///
+    /// If several elements are equally maximal, the last one is returned.
+    pub fn max(&self) -> Option<&T> {
+        self.iter().max()
+    }
+
+    /// Returns the index of the minimum element, or `None` if empty.
+    ///
+    /// If several elements are equally minimal, the first index is returned.
+    pub fn position_min(&self) -> Option<usize> {
+        self.iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the index of the maximum element, or `None` if empty.
+    ///
+    /// If several elements are equally maximal, the last index is returned.
+    pub fn position_max(&self) -> Option<usize> {
+        self.iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    /// Reorders the vector so that the element at `index` is at its sorted position.
+    ///
+    /// All elements before `index` compare less than or equal to it, and all
+    /// elements after compare greater than or equal to it, as with
+    /// `slice::select_nth_unstable`. Only the pointer vector is reordered; no
+    /// elements are moved or allocated. Returns a reference to the element at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![5, 1, 4, 2, 3]);
+    /// assert_eq!(*vec.select_nth_unstable(2), 3);
+    /// ```
+    pub fn select_nth_unstable(&mut self, index: usize) -> &T {
+        let items = self.items_mut();
+        items.select_nth_unstable_by(index, |a, b| {
+            // SAFETY: Pointers are valid for arena's lifetime
+            unsafe { a.as_ref().cmp(b.as_ref()) }
+        });
+        // SAFETY: Same as get() - pointer is valid for arena's lifetime
+        unsafe { items[index].as_ref() }
+    }
+}
+
+impl<T: Clone> CowVec<T> {
+    /// Reduces the elements to one by repeatedly applying `f`, front to back.
+    ///
+    /// The first element is cloned as the initial accumulator. Returns `None`
+    /// if the vector is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![3, 7, 2]);
+    /// assert_eq!(vec.reduce(|a, b| *a.max(b)), Some(7));
+    /// ```
+    pub fn reduce<F>(&self, mut f: F) -> Option<T>
+    where
+        F: FnMut(&T, &T) -> T,
+    {
+        let mut iter = self.iter();
+        let first = iter.next()?.clone();
+        Some(iter.fold(first, |acc, item| f(&acc, item)))
+    }
+
+    /// Clones the elements into a fixed-size array if the length is exactly `N`.
+    ///
+    /// Otherwise returns an O(1) clone of this vector in the `Err`, so the
+    /// caller keeps a handle to the data.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let rgba = CowVec::from(vec![255u8, 128, 0, 255]);
+    /// let [r, g, b, a] = rgba.try_into_array::<4>().unwrap();
+    /// assert_eq!((r, g, b, a), (255, 128, 0, 255));
+    /// assert!(rgba.try_into_array::<3>().is_err());
+    /// ```
+    pub fn try_into_array<const N: usize>(&self) -> Result<[T; N], CowVec<T>> {
+        if self.len() != N {
+            return Err(self.clone());
+        }
+        Ok(std::array::from_fn(|i| self[i].clone()))
+    }
+
+    /// Converts this `CowVec` into a `Vec` by cloning all elements.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+
+    /// Inserts clones of `values` at position `index`, shifting all elements after it to the right.
+    ///
+    /// The values are allocated in the arena as one batch and the tail is
+    /// shifted only once, unlike repeated calls to `insert`.
+    ///
+    /// # Panics
+    /// Panics if `index > len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 4]);
+    /// vec.insert_slice(1, &[2, 3]);
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3, 4]);
+    /// ```
+    pub fn insert_slice(&mut self, index: usize, values: &[T]) {
+        if index > self.items.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+        let ptrs = self.arena.alloc_extend(values.iter().cloned());
+        self.items_mut().splice(index..index, ptrs);
+    }
+
+    /// Returns a new `CowVec` holding this vector's elements followed by clones
+    /// of `other`.
+    ///
+    /// The result shares this vector's arena: existing elements are referenced
+    /// by pointer, and only the elements of `other` are cloned into the arena.
+    /// `self` is left unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2]);
+    /// let combined = vec.concat_with(&[3, 4]);
+    /// assert_eq!(combined.to_vec(), vec![1, 2, 3, 4]);
+    /// assert_eq!(vec.to_vec(), vec![1, 2]);
+    /// assert!(combined.shares_arena_with(&vec));
+    /// ```
+    pub fn concat_with(&self, other: &[T]) -> Self {
+        let mut items = Vec::with_capacity(self.len() + other.len());
+        items.extend_from_slice(&self.items);
+        items.extend(self.arena.alloc_extend(other.iter().cloned()));
+        Self {
+            arena: Arc::clone(&self.arena),
+            items: Arc::new(items),
+        }
+    }
+
+    /// Appends clones of the elements in `range` to the end of the vector.
+    ///
+    /// Matches `Vec::extend_from_within`: the range is resolved against the
+    /// current contents, before any elements are appended.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec!['a', 'b', 'c']);
+    /// vec.extend_from_within(0..2);
+    /// assert_eq!(vec.to_vec(), vec!['a', 'b', 'c', 'a', 'b']);
+    /// ```
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = self.resolve_range(range);
+        let values: Vec<T> = self.as_slice()[start..end]
+            .iter()
+            .map(|&item| item.clone())
+            .collect();
+        let ptrs = self.arena.alloc_extend(values);
+        self.items_mut().extend(ptrs);
+    }
+
+    /// Sets every element in `range` to a clone of `value`.
+    ///
+    /// Each index gets its own arena slot (allocated in a single batch), and
+    /// only this instance's pointers in the range are rewired; clones and
+    /// elements outside the range are unaffected.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// vec.fill_range(1..4, 0);
+    /// assert_eq!(vec.to_vec(), vec![1, 0, 0, 0, 5]);
+    /// ```
+    pub fn fill_range<R: RangeBounds<usize>>(&mut self, range: R, value: T) {
+        let (start, end) = self.resolve_range(range);
+        // Validate before allocating: `end - start` must not underflow.
+        if start > end {
+            panic!("slice index starts at {} but ends at {}", start, end);
+        }
+        if end > self.len() {
+            panic!(
+                "range end index {} out of range for slice of length {}",
+                end,
+                self.len()
+            );
+        }
+        let ptrs = self
+            .arena
+            .alloc_extend(std::iter::repeat_n(value, end - start));
+        self.items_mut()[start..end].copy_from_slice(&ptrs);
+    }
+
+    /// Overwrites all elements with clones of the elements in `src`.
+    ///
+    /// Matches `slice::clone_from_slice`. The new values are allocated in the
+    /// arena (copy-on-write), so other clones of this `CowVec` are unaffected.
+    ///
+    /// # Panics
+    /// Panics if `src.len() != len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// vec.clone_from_slice(&[4, 5, 6]);
+    /// assert_eq!(vec.to_vec(), vec![4, 5, 6]);
+    /// ```
+    pub fn clone_from_slice(&mut self, src: &[T]) {
+        assert_eq!(
+            self.len(),
+            src.len(),
+            "destination and source slices have different lengths"
+        );
+        let ptrs = self.arena.alloc_extend(src.iter().cloned());
+        self.items_mut().copy_from_slice(&ptrs);
+    }
+
+    /// Swaps all elements with the elements in `other`.
+    ///
+    /// Matches `slice::swap_with_slice`. Since arena values are never moved
+    /// out, `other` receives clones of this vector's elements, while the values
+    /// taken from `other` are moved into the arena. Other clones of this
+    /// `CowVec` are unaffected.
+    ///
+    /// # Panics
+    /// Panics if `other.len() != len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// let mut other = [4, 5, 6];
+    /// vec.swap_with_slice(&mut other);
+    /// assert_eq!(vec.to_vec(), vec![4, 5, 6]);
+    /// assert_eq!(other, [1, 2, 3]);
+    /// ```
+    pub fn swap_with_slice(&mut self, other: &mut [T]) {
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "destination and source slices have different lengths"
+        );
+        // Clone everything before touching `other`, so a panicking `clone`
+        // leaves both sides unchanged.
+        let mut values = self.to_vec();
+        for (theirs, ours) in other.iter_mut().zip(&mut values) {
+            std::mem::swap(theirs, ours);
+        }
+        let ptrs = self.arena.alloc_extend(values);
+        self.items_mut().copy_from_slice(&ptrs);
+    }
+
+    /// Converts this `CowVec` into a boxed slice by cloning all elements.
+    pub fn to_boxed_slice(&self) -> Box<[T]> {
+        self.to_vec().into_boxed_slice()
+    }
+
+    /// Converts this `CowVec` into a boxed slice, consuming it.
+    ///
+    /// If this instance is the sole owner of its arena, the live elements are
+    /// moved out without cloning (only elements referenced from several slots
+    /// are cloned). Otherwise, this falls back to [`to_boxed_slice`].
+    ///
+    /// [`to_boxed_slice`]: CowVec::to_boxed_slice
+    pub fn into_boxed_slice(self) -> Box<[T]> {
+        let CowVec { arena, items } = self;
+        match Arc::try_unwrap(arena) {
+            Ok(arena) => {
+                let staged = arena.stage_values(&items);
+                arena.into_values(staged).0.into_boxed_slice()
+            }
+            Err(arena) => CowVec { arena, items }.to_boxed_slice(),
+        }
+    }
+
+    /// Converts this `CowVec` into an owned `Cow<[T]>` by cloning all elements.
+    ///
+    /// Elements live behind individual arena pointers rather than in a
+    /// contiguous buffer, so the result is always `Cow::Owned`.
+    pub fn to_cow(&self) -> Cow<'_, [T]> {
+        Cow::Owned(self.to_vec())
+    }
+
+    /// Creates a `CowVec` from a `Cow<[T]>`.
+    ///
+    /// Owned data is moved into the arena; borrowed data is cloned.
+    ///
+    /// # Example
+    /// ```
+    /// use std::borrow::Cow;
+    /// use cow_vec::CowVec;
+    ///
+    /// let data = [1, 2, 3];
+    /// let vec = CowVec::from_cow(Cow::Borrowed(&data[..]));
+    /// assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn from_cow(cow: Cow<'_, [T]>) -> Self {
+        Self::from(cow.into_owned())
+    }
+
+    /// Removes the last element and returns a clone of it, or `None` if empty.
+    ///
+    /// Unlike [`pop`](CowVec::pop), the returned value is owned and does not
+    /// borrow this vector.
+    pub fn pop_owned(&mut self) -> Option<T> {
+        self.pop().cloned()
+    }
+
+    /// Removes the element at the given index and returns a clone of it.
+    ///
+    /// All elements after the index are shifted left. Unlike
+    /// [`remove`](CowVec::remove), the returned value is owned and does not
+    /// borrow this vector.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
+    pub fn remove_owned(&mut self, index: usize) -> T {
+        self.remove(index).clone()
+    }
+
+    /// Removes the element at the given index and returns a clone of it,
+    /// replacing it with the last element.
+    ///
+    /// This does not preserve ordering but is O(1). Like
+    /// [`remove_owned`](CowVec::remove_owned), the returned value is owned and
+    /// does not borrow this vector.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4]);
+    /// assert_eq!(vec.swap_remove_owned(0), 1);
+    /// assert_eq!(vec.to_vec(), vec![4, 2, 3]);
+    /// ```
+    pub fn swap_remove_owned(&mut self, index: usize) -> T {
+        let ptr = self.items_mut().swap_remove(index);
+        // SAFETY: Same as get() - pointer is valid for arena's lifetime
+        unsafe { ptr.as_ref() }.clone()
+    }
+
+    /// Modifies the element at the given index through a closure.
+    ///
+    /// The current value is cloned exactly once, `f` is applied to the copy, and
+    /// the result is allocated in the arena: one allocation in total, unlike
+    /// `IndexMut`, which allocates on every access. Other clones of this
+    /// `CowVec` continue to see the original value.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// vec.modify(0, |x| *x += 5);
+    /// assert_eq!(vec.to_vec(), vec![6, 2, 3]);
+    /// ```
+    pub fn modify<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        if index >= self.items.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+        let mut value = self[index].clone();
+        f(&mut value);
+        self.set(index, value);
+    }
+
+    /// Returns mutable references to the elements at two distinct indices.
+    ///
+    /// Both elements are cloned into fresh arena slots and this instance's
+    /// pointers are rewired before the references are handed out, so edits
+    /// never reach clones. Returns `None` if `a == b` or either index is out
+    /// of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![10, 1, 5]);
+    /// let (from, to) = vec.get2_mut(0, 2).unwrap();
+    /// *from -= 3;
+    /// *to += 3;
+    /// assert_eq!(vec.to_vec(), vec![7, 1, 8]);
+    /// ```
+    pub fn get2_mut(&mut self, a: usize, b: usize) -> Option<(&mut T, &mut T)> {
+        if a == b || a >= self.len() || b >= self.len() {
+            return None;
+        }
+        let ptrs = self.arena.alloc_extend([self[a].clone(), self[b].clone()]);
+        let items = self.items_mut();
+        items[a] = ptrs[0];
+        items[b] = ptrs[1];
+        // SAFETY: Both slots were just allocated, are distinct, and are
+        // referenced only by this vector, which stays mutably borrowed for
+        // the lifetime of the returned references.
+        unsafe { Some((&mut *ptrs[0].as_ptr(), &mut *ptrs[1].as_ptr())) }
+    }
+
+    /// Returns an iterator yielding `(index, &mut T)` for each element.
+    ///
+    /// Before an element is handed out, it is cloned into a fresh arena slot
+    /// and this instance's pointer is rewired to it, so edits never reach
+    /// clones. Every visited element allocates, whether or not it is actually
+    /// modified; elements not reached (e.g. after breaking out early) keep
+    /// sharing their original slot.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![10, 20, 30]);
+    /// let original = vec.clone();
+    /// for (i, x) in vec.iter_mut_enumerated() {
+    ///     *x += i as i32;
+    /// }
+    /// assert_eq!(vec.to_vec(), vec![10, 21, 32]);
+    /// assert_eq!(original.to_vec(), vec![10, 20, 30]);
+    /// ```
+    pub fn iter_mut_enumerated(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        let arena = &*self.arena;
+        Arc::make_mut(&mut self.items)
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, slot)| {
+                // SAFETY: Same as get() - pointer is valid for arena's lifetime
+                let value = unsafe { slot.as_ref() }.clone();
+                let ptr = arena.alloc(value);
+                *slot = ptr;
+                // SAFETY: The slot was just allocated and is referenced only by
+                // this vector's pointer at `index`, which stays mutably borrowed
+                // for the iterator's lifetime, so the `&mut T` is unique.
+                (index, unsafe { &mut *ptr.as_ptr() })
+            })
+    }
+
+    /// Clones this `CowVec`, creating a fresh arena if the current one exceeds max_capacity.
+    ///
+    /// If the arena's allocation count exceeds `max_capacity`, a new arena is created
+    /// containing only the current elements (compacting the data). Otherwise, the arena
+    /// is shared as with regular `clone()`.
+    ///
+    /// This is useful for controlling memory growth when the arena has accumulated
+    /// many allocations from `push`, `set`, or garbage from `pop`/`remove` operations.
+    pub fn clone_with_max_capacity(&self, max_capacity: usize) -> Self {
+        if self.arena.len() <= max_capacity {
+            return self.clone();
+        }
+
+        self.deep_clone()
+    }
+
+    /// Compacts the arena in place if this vector is its sole owner.
+    ///
+    /// When no clone, `split_off` result, or [`ElemHandle`] shares the arena,
+    /// the live elements are moved into a fresh arena and all dead values are
+    /// dropped; returns `true`. While the arena is shared this does nothing
+    /// and returns `false`.
+    ///
+    /// Unlike [`deep_clone`](CowVec::deep_clone), elements are moved rather
+    /// than cloned (except ones referenced from several slots).
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    /// vec.truncate(2);
+    /// assert!(vec.try_reclaim());
+    /// assert!(vec.is_compact());
+    /// ```
+    pub fn try_reclaim(&mut self) -> bool {
+        if Arc::get_mut(&mut self.arena).is_none() {
+            return false;
+        }
+        // Run every clone while the old arena is still in place, so that a
+        // panicking `clone` leaves this vector untouched.
+        let staged = self.arena.stage_values(&self.items);
+        let old_arena = std::mem::replace(
+            &mut self.arena,
+            Arc::new(CowArena::with_capacity(staged.len())),
+        );
+        let (values, dead) = match Arc::try_unwrap(old_arena) {
+            Ok(arena) => arena.into_values(staged),
+            Err(_) => unreachable!("arena was uniquely owned"),
+        };
+        *self.items_mut() = self.arena.alloc_extend(values);
+        // Dead values are dropped only once this vector points into the new
+        // arena, so a panicking destructor cannot leave it dangling.
+        drop(dead);
+        true
+    }
+
+    /// Compacts the arena in place if it holds more than `max_capacity`
+    /// allocations and this vector is its sole owner.
+    ///
+    /// The in-place mirror of
+    /// [`clone_with_max_capacity`](CowVec::clone_with_max_capacity), for a
+    /// single long-lived vector: compaction goes through
+    /// [`try_reclaim`](CowVec::try_reclaim), so it does nothing while the arena
+    /// is shared. Returns `true` if the arena was compacted.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// for i in 0..100 {
+    ///     vec.set(0, i);
+    /// }
+    /// assert!(vec.compact_to_capacity(10));
+    /// assert!(vec.is_compact());
+    /// ```
+    pub fn compact_to_capacity(&mut self, max_capacity: usize) -> bool {
+        self.arena.len() > max_capacity && self.try_reclaim()
+    }
+
+    /// Shortens the vector like [`truncate`](CowVec::truncate), then compacts
+    /// the arena if this vector is its sole owner.
+    ///
+    /// When elements were actually removed and no clone or handle shares the
+    /// arena, the removed values (and any other dead values) are dropped via
+    /// [`try_reclaim`](CowVec::try_reclaim), which moves the remaining `len`
+    /// elements into a fresh arena. While the arena is shared, this behaves
+    /// exactly like `truncate`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from((0..1000).collect::<Vec<i32>>());
+    /// vec.truncate_and_reclaim(10);
+    /// assert_eq!(vec.len(), 10);
+    /// assert!(vec.is_compact());
+    /// ```
+    pub fn truncate_and_reclaim(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        self.truncate(len);
+        self.try_reclaim();
+    }
+
+    /// Takes a durable checkpoint of this vector in a fresh arena.
+    ///
+    /// An alias of [`deep_clone`](CowVec::deep_clone), named for version
+    /// histories built as chains of clones: those share one arena that keeps
+    /// accumulating garbage. A snapshot holds only the live elements and
+    /// shares nothing, so once the older versions are dropped their arena is
+    /// freed.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut doc = CowVec::from(vec!["draft"]);
+    /// doc.set(0, "final");
+    /// let checkpoint = doc.snapshot();
+    /// drop(doc);
+    /// assert!(checkpoint.is_compact());
+    /// ```
+    pub fn snapshot(&self) -> Self {
+        self.deep_clone()
+    }
+
+    /// Clones this `CowVec` into a fresh arena containing only its current elements.
+    ///
+    /// Unlike `clone()`, the result shares nothing with `self`, so it does not
+    /// keep the source's arena (and any garbage in it) alive. This costs O(n)
+    /// element clones.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec![1, 2, 3]);
+    /// let copy = vec.deep_clone();
+    /// assert!(!copy.shares_arena_with(&vec));
+    /// assert_eq!(copy.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn deep_clone(&self) -> Self {
+        let new_arena = Arc::new(CowArena::with_capacity(self.len()));
+        let new_items = new_arena.alloc_extend(self.iter().cloned());
+
+        Self {
+            arena: new_arena,
+            items: Arc::new(new_items),
+        }
+    }
+}
+
+impl<T: Clone> CowVec<CowVec<T>> {
+    /// Flattens the inner vectors into a single `CowVec`.
+    ///
+    /// If all inner vectors share one arena (e.g. they were produced from a
+    /// common parent by `split_off`, `subvec`, or `clone`), the result shares
+    /// that arena too and only pointers are concatenated. Otherwise, elements
+    /// are cloned into a fresh arena.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut parent = CowVec::from(vec![1, 2, 3, 4]);
+    /// let tail = parent.split_off(2);
+    /// let nested = CowVec::from(vec![tail, parent.clone()]);
+    /// let flat = nested.concat();
+    /// assert_eq!(flat.to_vec(), vec![3, 4, 1, 2]);
+    /// assert!(flat.shares_arena_with(&parent));
+    /// ```
+    pub fn concat(&self) -> CowVec<T> {
+        let Some(first) = self.first() else {
+            return CowVec::new();
+        };
+        if self.iter().all(|inner| inner.shares_arena_with(first)) {
+            let items: Vec<NonNull<T>> = self
+                .iter()
+                .flat_map(|inner| inner.items.iter().copied())
+                .collect();
+            CowVec {
+                arena: Arc::clone(&first.arena),
+                items: Arc::new(items),
+            }
+        } else {
+            let total = self.iter().map(|inner| inner.len()).sum();
+            let mut values = Vec::with_capacity(total);
+            for inner in self.iter() {
+                values.extend(inner.iter().cloned());
+            }
+            CowVec::from(values)
+        }
     }
 
-    /// Inserts an element at position `index`, shifting all elements after it to the right.
+    /// Flattens the inner vectors into a single `CowVec`, placing a clone of
+    /// `sep` between each pair of adjacent inner vectors.
     ///
-    /// # Panics
-    /// Panics if `index > len()`.
+    /// Mirrors `slice::join`. As with [`concat`](CowVec::concat), if all inner
+    /// vectors share one arena the result shares it too; the separator is then
+    /// allocated once and referenced from every gap.
     ///
     /// # Example
     /// ```
     /// use cow_vec::CowVec;
     ///
-    /// let mut vec = CowVec::from(vec![1, 2, 3]);
-    /// vec.insert(1, 10);
-    /// assert_eq!(vec.to_vec(), vec![1, 10, 2, 3]);
+    /// let nested = CowVec::from(vec![
+    ///     CowVec::from(vec![1, 2]),
+    ///     CowVec::from(vec![3]),
+    ///     CowVec::from(vec![4, 5]),
+    /// ]);
+    /// assert_eq!(nested.join(&0).to_vec(), vec![1, 2, 0, 3, 0, 4, 5]);
     /// ```
-    pub fn insert(&mut self, index: usize, value: T) {
-        let ptr = self.arena.alloc(value);
-        self.items_mut().insert(index, ptr);
+    pub fn join(&self, sep: &T) -> CowVec<T> {
+        let Some(first) = self.first() else {
+            return CowVec::new();
+        };
+        if self.iter().all(|inner| inner.shares_arena_with(first)) {
+            let mut sep_ptr = None;
+            let mut items: Vec<NonNull<T>> = Vec::new();
+            for (i, inner) in self.iter().enumerate() {
+                if i > 0 {
+                    items.push(*sep_ptr.get_or_insert_with(|| first.arena.alloc(sep.clone())));
+                }
+                items.extend(inner.items.iter().copied());
+            }
+            CowVec {
+                arena: Arc::clone(&first.arena),
+                items: Arc::new(items),
+            }
+        } else {
+            let mut values = Vec::new();
+            for (i, inner) in self.iter().enumerate() {
+                if i > 0 {
+                    values.push(sep.clone());
+                }
+                values.extend(inner.iter().cloned());
+            }
+            CowVec::from(values)
+        }
     }
+}
 
-    /// Retains only the elements specified by the predicate.
+impl<T: Copy> CowVec<T> {
+    /// Writes the vector in a compact binary form: the element count as a
+    /// little-endian `u64`, followed by the raw bytes of each element.
     ///
-    /// Removes all elements for which the predicate returns `false`.
+    /// Element bytes are written in the platform's native endianness and
+    /// layout, so the output is only portable between machines that agree on
+    /// both. Read it back with [`read_from`](CowVec::read_from).
     ///
-    /// Note: Removed values remain in the shared arena.
+    /// # Safety
+    /// `T` must contain no padding bytes (e.g. primitive integers and floats,
+    /// or `#[repr(C)]` structs of them laid out without gaps). Reading padding
+    /// as bytes is undefined behavior.
     ///
     /// # Example
     /// ```
     /// use cow_vec::CowVec;
     ///
-    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
-    /// vec.retain(|&x| x % 2 == 0);
-    /// assert_eq!(vec.to_vec(), vec![2, 4]);
+    /// let vec = CowVec::from(vec![1u32, 2, 3]);
+    /// let mut buf = Vec::new();
+    /// unsafe { vec.write_to(&mut buf) }.unwrap();
+    /// let back = unsafe { CowVec::<u32>::read_from(&mut buf.as_slice(), 3) }.unwrap();
+    /// assert_eq!(back, vec);
     /// ```
-    pub fn retain<F>(&mut self, mut f: F)
-    where
-        F: FnMut(&T) -> bool,
-    {
-        self.items_mut().retain(|ptr| {
-            // SAFETY: Pointer is valid for arena's lifetime
-            let value = unsafe { &**ptr };
-            f(value)
-        });
+    pub unsafe fn write_to<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(&(self.len() as u64).to_le_bytes())?;
+        for item in self.iter() {
+            // SAFETY: The caller guarantees `T` has no padding, so all
+            // `size_of::<T>()` bytes of the element are initialized.
+            let bytes = unsafe {
+                std::slice::from_raw_parts(
+                    (item as *const T).cast::<u8>(),
+                    std::mem::size_of::<T>(),
+                )
+            };
+            w.write_all(bytes)?;
+        }
+        Ok(())
     }
 
-    /// Splits the vector into two at the given index.
+    /// Reads a vector written by [`write_to`](CowVec::write_to).
     ///
-    /// Returns a new `CowVec` containing elements from `at` to the end.
-    /// After this call, `self` contains elements `[0, at)` and the returned
-    /// `CowVec` contains elements `[at, len)`.
+    /// `len` is the expected element count; an `InvalidData` error is returned
+    /// if the stored count differs. All elements are read with a single
+    /// `read_exact` call and allocated into a fresh arena.
     ///
-    /// Both vectors share the same arena, so this is an efficient operation.
+    /// # Safety
+    /// Every bit pattern of `size_of::<T>()` bytes must be a valid `T` (this
+    /// rules out `bool`, `char`, enums, references, etc.), or the input must
+    /// come from `write_to` on the same platform for the same `T`.
+    pub unsafe fn read_from<R: io::Read>(r: &mut R, len: usize) -> io::Result<Self> {
+        let mut prefix = [0u8; 8];
+        r.read_exact(&mut prefix)?;
+        if u64::from_le_bytes(prefix) != len as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stored element count does not match the expected length",
+            ));
+        }
+
+        let size = std::mem::size_of::<T>();
+        let total = len.checked_mul(size).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "element count overflows usize")
+        })?;
+        let mut buf = vec![0u8; total];
+        r.read_exact(&mut buf)?;
+
+        let values: Vec<T> = if size == 0 {
+            // SAFETY: Any zero-sized value is valid to conjure from nothing.
+            (0..len).map(|_| unsafe { std::mem::zeroed() }).collect()
+        } else {
+            buf.chunks_exact(size)
+                // SAFETY: Each chunk holds `size_of::<T>()` initialized bytes,
+                // and the caller guarantees they form a valid `T`.
+                .map(|chunk| unsafe { chunk.as_ptr().cast::<T>().read_unaligned() })
+                .collect()
+        };
+        Ok(Self::from(values))
+    }
+}
+
+impl<T> CowVec<T> {
+    /// Sets the value at the given index.
+    ///
+    /// This implements copy-on-write semantics: a new entry is allocated in the
+    /// arena with the given value, and only this instance's pointer is updated.
+    /// Other clones of this `CowVec` continue to see the original value.
     ///
     /// # Panics
-    /// Panics if `at > len()`.
+    /// Panics if `index >= len()`.
+    pub fn set(&mut self, index: usize, value: T) {
+        if index >= self.items.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+        let ptr = self.arena.alloc(value);
+        self.items_mut()[index] = ptr;
+    }
+
+    /// Sets the value at the given index, overwriting the existing arena slot
+    /// when that is provably unobservable.
+    ///
+    /// The write happens in place (dropping the old value, with no new arena
+    /// allocation) only if both of these hold:
+    /// - this `CowVec` is the sole owner of its arena, so no clone, `split_off`
+    ///   or `subvec` result, or [`ElemHandle`] can observe the slot; and
+    /// - the slot's pointer appears at no other index of this vector. Any
+    ///   operation that copies pointers rather than values can repeat one,
+    ///   e.g. `copy_within`, or `concat` and `join` over a shared arena.
+    ///
+    /// Otherwise this falls back to [`set`](CowVec::set). Because of the
+    /// duplicate check the call is O(n), but it never grows the arena when the
+    /// vector is unshared, which suits long-lived vectors updated in a loop.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
     ///
     /// # Example
     /// ```
     /// use cow_vec::CowVec;
     ///
-    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
-    /// let tail = vec.split_off(3);
-    /// assert_eq!(vec.to_vec(), vec![1, 2, 3]);
-    /// assert_eq!(tail.to_vec(), vec![4, 5]);
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// let before = vec.as_ptr_slice()[0];
+    /// vec.set_in_place(0, 10);
+    /// assert_eq!(vec[0], 10);
+    /// assert_eq!(vec.as_ptr_slice()[0], before);
     /// ```
-    pub fn split_off(&mut self, at: usize) -> Self {
-        let tail_items = self.items_mut().split_off(at);
-        Self {
-            arena: Arc::clone(&self.arena),
-            items: Arc::new(tail_items),
+    pub fn set_in_place(&mut self, index: usize, value: T) {
+        if index >= self.items.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            );
+        }
+        match self.uniquely_owned_slot(index) {
+            // SAFETY: See `uniquely_owned_slot`; the old value is dropped in place.
+            Some(ptr) => unsafe { *ptr.as_ptr() = value },
+            None => self.set(index, value),
         }
     }
 
-    /// Removes the specified range and replaces it with elements from the iterator.
+    /// Returns a mutable reference to the element at `index`, without
+    /// allocating, if the slot is provably not observable from anywhere else.
     ///
-    /// Returns the removed elements as a `Vec` of references.
+    /// Returns `Some` only under the same conditions in which
+    /// [`set_in_place`](CowVec::set_in_place) writes in place: this `CowVec`
+    /// is the sole owner of its arena, and no other index of it points at the
+    /// same slot.
     ///
-    /// # Panics
-    /// Panics if the range is out of bounds.
+    /// Returns `None` if `index` is out of bounds or either condition fails;
+    /// callers should then fall back to [`modify`](CowVec::modify) or
+    /// [`set`](CowVec::set). The duplicate check makes this O(n).
     ///
     /// # Example
     /// ```
     /// use cow_vec::CowVec;
     ///
-    /// let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
-    /// let removed: Vec<&i32> = vec.splice(1..3, vec![10, 20, 30]);
-    /// assert_eq!(removed, vec![&2, &3]);
-    /// assert_eq!(vec.to_vec(), vec![1, 10, 20, 30, 4, 5]);
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// *vec.try_get_mut(1).unwrap() += 10;
+    /// assert_eq!(vec.to_vec(), vec![1, 12, 3]);
+    ///
+    /// let clone = vec.clone();
+    /// assert!(vec.try_get_mut(1).is_none());
+    /// # drop(clone);
     /// ```
-    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Vec<&T>
-    where
-        R: RangeBounds<usize>,
-        I: IntoIterator<Item = T>,
-    {
-        let start = match range.start_bound() {
-            Bound::Included(&n) => n,
-            Bound::Excluded(&n) => n + 1,
-            Bound::Unbounded => 0,
-        };
-        let end = match range.end_bound() {
-            Bound::Included(&n) => n + 1,
-            Bound::Excluded(&n) => n,
-            Bound::Unbounded => self.len(),
-        };
-
-        // Allocate new elements in arena
-        let new_ptrs: Vec<*const T> = replace_with
-            .into_iter()
-            .map(|item| self.arena.alloc(item))
-            .collect();
-
-        // Splice the pointer vector and collect removed pointers
-        let removed_ptrs: Vec<*const T> = self.items_mut().splice(start..end, new_ptrs).collect();
-
-        // Convert removed pointers to references
-        removed_ptrs
-            .into_iter()
-            .map(|ptr| {
-                // SAFETY: Pointer is valid for arena's lifetime
-                unsafe { &*ptr }
-            })
-            .collect()
-    }
-}
-
-impl<T: PartialEq> CowVec<T> {
-    /// Returns `true` if the vector contains the given value.
-    pub fn contains(&self, value: &T) -> bool {
-        self.iter().any(|item| item == value)
+    pub fn try_get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let ptr = self.uniquely_owned_slot(index)?;
+        // SAFETY: See `uniquely_owned_slot`; the returned reference keeps
+        // `self` mutably borrowed, so no other access can happen meanwhile.
+        Some(unsafe { &mut *ptr.as_ptr() })
     }
-}
 
-impl<T: Clone> CowVec<T> {
-    /// Converts this `CowVec` into a `Vec` by cloning all elements.
-    pub fn to_vec(&self) -> Vec<T> {
-        self.iter().cloned().collect()
+    /// Returns the pointer at `index` if mutating its value in place cannot
+    /// be observed through any other vector, handle, or index.
+    ///
+    /// Both conditions are checked directly rather than tracked:
+    /// - no other `CowVec` or `ElemHandle` holds this arena (`Arc::get_mut`
+    ///   succeeds), so nothing outside this vector can reach the slot; and
+    /// - no other index of this vector points at the slot. Any operation that
+    ///   copies pointers rather than values can repeat one, so the whole
+    ///   pointer vector is scanned instead of relying on a list of them.
+    ///
+    /// Together with `&mut self`, this guarantees no reference to the value is
+    /// alive. The pointer came from a `&mut T` handed out by the arena, so
+    /// writing through it is allowed.
+    fn uniquely_owned_slot(&mut self, index: usize) -> Option<NonNull<T>> {
+        let ptr = *self.items.get(index)?;
+        Arc::get_mut(&mut self.arena)?;
+        let aliased = self.items.iter().filter(|&&other| other == ptr).count() > 1;
+        (!aliased).then_some(ptr)
     }
 
-    /// Clones this `CowVec`, creating a fresh arena if the current one exceeds max_capacity.
+    /// Sets the value at the given index, without bounds checking.
     ///
-    /// If the arena's allocation count exceeds `max_capacity`, a new arena is created
-    /// containing only the current elements (compacting the data). Otherwise, the arena
-    /// is shared as with regular `clone()`.
+    /// Behaves like [`set`](CowVec::set) otherwise.
     ///
-    /// This is useful for controlling memory growth when the arena has accumulated
-    /// many allocations from `push`, `set`, or garbage from `pop`/`remove` operations.
-    pub fn clone_with_max_capacity(&self, max_capacity: usize) -> Self {
-        if self.arena.len() <= max_capacity {
-            return self.clone();
+    /// # Safety
+    /// `index` must be less than `len()`. Calling this with an out-of-bounds
+    /// index is undefined behavior.
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: T) {
+        let ptr = self.arena.alloc(value);
+        // SAFETY: The caller guarantees index < len().
+        unsafe {
+            *self.items_mut().get_unchecked_mut(index) = ptr;
         }
+    }
 
-        // Create a fresh arena with just the current elements.
-        let new_arena = Arc::new(CowArena::with_capacity(self.len()));
-        let new_items: Vec<*const T> = self
-            .iter()
-            .map(|item| new_arena.alloc(item.clone()))
-            .collect();
-
-        Self {
-            arena: new_arena,
-            items: Arc::new(new_items),
+    /// Sets the value at the given index and returns a reference to the previous value.
+    ///
+    /// Behaves like [`set`](CowVec::set). The previous value stays in the arena,
+    /// so it can be returned by reference, which is convenient for undo stacks.
+    ///
+    /// # Panics
+    /// Panics if `index >= len()`.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let mut vec = CowVec::from(vec![1, 2, 3]);
+    /// let old = vec.replace(0, 10);
+    /// assert_eq!(*old, 1);
+    /// assert_eq!(vec[0], 10);
+    /// ```
+    pub fn replace(&mut self, index: usize, value: T) -> &T {
+        if index >= self.items.len() {
+            panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            );
         }
+        let ptr = self.arena.alloc(value);
+        let old = std::mem::replace(&mut self.items_mut()[index], ptr);
+        // SAFETY: Same as get() - pointer is valid for arena's lifetime
+        unsafe { old.as_ref() }
     }
-}
 
-impl<T> CowVec<T> {
-    /// Sets the value at the given index.
+    /// Returns an [`Entry`] for the element at the given index.
     ///
-    /// This implements copy-on-write semantics: a new entry is allocated in the
-    /// arena with the given value, and only this instance's pointer is updated.
-    /// Other clones of this `CowVec` continue to see the original value.
+    /// The entry allows reading the element without allocating, and allocates
+    /// only when it is written to.
     ///
     /// # Panics
     /// Panics if `index >= len()`.
-    pub fn set(&mut self, index: usize, value: T) {
+    pub fn entry(&mut self, index: usize) -> Entry<'_, T> {
         if index >= self.items.len() {
             panic!(
                 "index out of bounds: the len is {} but the index is {}",
@@ -451,8 +3189,7 @@ impl<T> CowVec<T> {
                 index
             );
         }
-        let ptr = self.arena.alloc(value);
-        self.items_mut()[index] = ptr;
+        Entry { vec: self, index }
     }
 }
 
@@ -480,6 +3217,22 @@ impl<T> Clone for CowVec<T> {
             items: Arc::clone(&self.items),
         }
     }
+
+    /// Overwrites this `CowVec` with a clone of `source`, reusing allocations.
+    ///
+    /// If this instance exclusively owns its pointer vector, `source`'s pointers
+    /// are copied into the existing buffer instead of sharing `source`'s vector.
+    /// This costs an O(n) pointer copy up front but avoids both a fresh
+    /// allocation now and the deferred copy-on-write on the next mutation,
+    /// which suits loops that repeatedly overwrite and then mutate a buffer.
+    /// Otherwise, this behaves like `clone()`.
+    fn clone_from(&mut self, source: &Self) {
+        self.arena = Arc::clone(&source.arena);
+        match Arc::get_mut(&mut self.items) {
+            Some(items) => items.clone_from(&source.items),
+            None => self.items = Arc::clone(&source.items),
+        }
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for CowVec<T> {
@@ -488,11 +3241,130 @@ impl<T: fmt::Debug> fmt::Debug for CowVec<T> {
     }
 }
 
+impl<T: fmt::Display> fmt::Display for CowVec<T> {
+    /// Formats the elements separated by `", "` and wrapped in brackets.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    ///
+    /// let vec = CowVec::from(vec!["a", "b", "c"]);
+    /// assert_eq!(vec.to_string(), "[a, b, c]");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            fmt::Display::fmt(item, f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<T: Hash> Hash for CowVec<T> {
+    /// Hashes the length followed by each element, in order.
+    ///
+    /// Equal `CowVec`s hash identically regardless of how their arenas or
+    /// pointer vectors are shared.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for item in self.iter() {
+            item.hash(state);
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for CowVec<T> {
+    /// Compares two `CowVec`s element by element.
+    ///
+    /// Slots holding the same arena pointer are treated as equal without
+    /// dereferencing them, so comparing clones that share most of their
+    /// elements costs mostly pointer comparisons. If both vectors share the
+    /// same pointer vector, the comparison is O(1).
+    ///
+    /// Because of this shortcut, an element is always considered equal to
+    /// itself, even for types like `f64` where `NaN != NaN`.
+    fn eq(&self, other: &Self) -> bool {
+        if Arc::ptr_eq(&self.items, &other.items) {
+            return true;
+        }
+        self.len() == other.len()
+            && self.items.iter().zip(other.items.iter()).all(|(a, b)| {
+                // SAFETY: Pointers are valid for their arenas' lifetimes
+                a == b || unsafe { a.as_ref() == b.as_ref() }
+            })
+    }
+}
+
+impl<T: Eq> Eq for CowVec<T> {}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for CowVec<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq> PartialEq<[T]> for CowVec<T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq> PartialEq<&[T]> for CowVec<T> {
+    fn eq(&self, other: &&[T]) -> bool {
+        self.eq_slice(other)
+    }
+}
+
+impl<T: PartialEq> PartialEq<CowVec<T>> for Vec<T> {
+    fn eq(&self, other: &CowVec<T>) -> bool {
+        other.eq_slice(self)
+    }
+}
+
+impl<T: PartialEq> PartialEq<CowVec<T>> for [T] {
+    fn eq(&self, other: &CowVec<T>) -> bool {
+        other.eq_slice(self)
+    }
+}
+
+impl<T: PartialEq> PartialEq<CowVec<T>> for &[T] {
+    fn eq(&self, other: &CowVec<T>) -> bool {
+        other.eq_slice(self)
+    }
+}
+
 impl<T> From<Vec<T>> for CowVec<T> {
     /// Creates a `CowVec` from a `Vec`.
     fn from(vec: Vec<T>) -> Self {
         let arena = Arc::new(CowArena::with_capacity(vec.len()));
-        let items: Vec<*const T> = vec.into_iter().map(|item| arena.alloc(item)).collect();
+        let items = arena.alloc_extend(vec);
+        Self {
+            arena,
+            items: Arc::new(items),
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for CowVec<T> {
+    /// Creates a `CowVec` from a slice by cloning each element into a new arena.
+    fn from(slice: &[T]) -> Self {
+        let arena = Arc::new(CowArena::with_capacity(slice.len()));
+        let items = arena.alloc_extend(slice.iter().cloned());
+        Self {
+            arena,
+            items: Arc::new(items),
+        }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for CowVec<T> {
+    /// Creates a `CowVec` from an array by moving its elements into a new arena.
+    fn from(array: [T; N]) -> Self {
+        let arena = Arc::new(CowArena::with_capacity(N));
+        let items = arena.alloc_extend(array);
         Self {
             arena,
             items: Arc::new(items),
@@ -548,6 +3420,19 @@ impl<T> Index<usize> for CowVec<T> {
 /// vec.set(0, vec[0] + 1);     // Clear: allocates once
 /// ```
 ///
+/// When a write depends on the current value, `entry()` reads without allocating
+/// and allocates only if the entry is actually written:
+///
+/// ```
+/// use cow_vec::CowVec;
+///
+/// let mut vec = CowVec::from(vec![1, 2, 3]);
+/// let mut entry = vec.entry(0);
+/// if *entry.get() > 0 {
+///     entry.modify(|x| *x += 1);  // Allocates once, only on this branch
+/// }
+/// ```
+///
 /// Only use `IndexMut` when you need compatibility with code expecting `&mut T`.
 impl<T: Clone> IndexMut<usize> for CowVec<T> {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
@@ -559,11 +3444,11 @@ impl<T: Clone> IndexMut<usize> for CowVec<T> {
             );
         }
         // Clone the current value to a new arena location (copy-on-write).
-        let current = unsafe { &*self.items[index] }.clone();
+        let current = unsafe { self.items[index].as_ref() }.clone();
         let ptr = self.arena.alloc(current);
         self.items_mut()[index] = ptr;
         // SAFETY: The pointer was just allocated and is valid. We have exclusive
         // access via &mut self. The arena allocates mutable memory.
-        unsafe { &mut *(ptr as *mut T) }
+        unsafe { &mut *ptr.as_ptr() }
     }
 }
@@ -0,0 +1,55 @@
+use super::CowVec;
+
+/// A view into a single element of a `CowVec`, obtained via [`CowVec::entry`].
+///
+/// Reading through an `Entry` never allocates. A new arena value is allocated
+/// only when the entry is actually written via [`set`](Entry::set) or
+/// [`modify`](Entry::modify), which makes it a cheaper alternative to
+/// `IndexMut` for read-then-conditionally-write code.
+///
+/// # Example
+/// ```
+/// use cow_vec::CowVec;
+///
+/// let mut vec = CowVec::from(vec![1, 2, 3]);
+/// let mut entry = vec.entry(0);
+/// if *entry.get() < 10 {
+///     entry.modify(|x| *x += 1);
+/// }
+/// assert_eq!(vec[0], 2);
+/// ```
+pub struct Entry<'a, T> {
+    pub(super) vec: &'a mut CowVec<T>,
+    pub(super) index: usize,
+}
+
+impl<T> Entry<'_, T> {
+    /// Returns the index of this entry.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a reference to the current value. Does not allocate.
+    pub fn get(&self) -> &T {
+        &self.vec[self.index]
+    }
+
+    /// Replaces the value, allocating it in the arena.
+    ///
+    /// Other clones of the vector continue to see the original value.
+    pub fn set(&mut self, value: T) {
+        self.vec.set(self.index, value);
+    }
+}
+
+impl<T: Clone> Entry<'_, T> {
+    /// Clones the current value, applies `f` to the copy, and stores the result.
+    ///
+    /// Performs exactly one arena allocation.
+    pub fn modify<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        self.vec.modify(self.index, f);
+    }
+}
@@ -0,0 +1,115 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use super::CowVec;
+
+/// A `CowVec` wrapper that caches its content hash, for use as a map key.
+///
+/// Hashing a large `CowVec` walks every element. `HashCached` computes that
+/// hash once, on the first [`Hash`] call, and feeds the cached value to every
+/// later hasher. Any mutable access through [`get_mut`](HashCached::get_mut)
+/// discards the cache, so the next hash reflects the new contents.
+///
+/// Each wrapper owns its cache: mutating a clone never affects the original.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use cow_vec::{CowVec, HashCached};
+///
+/// let key = HashCached::new(CowVec::from(vec![1, 2, 3]));
+/// let mut map = HashMap::new();
+/// map.insert(key.clone(), "first");
+/// assert_eq!(map.get(&key), Some(&"first"));
+/// ```
+pub struct HashCached<T> {
+    vec: CowVec<T>,
+    hash: OnceLock<u64>,
+}
+
+impl<T> HashCached<T> {
+    /// Wraps `vec` with an empty hash cache.
+    pub fn new(vec: CowVec<T>) -> Self {
+        Self {
+            vec,
+            hash: OnceLock::new(),
+        }
+    }
+
+    /// Returns a reference to the wrapped `CowVec`.
+    pub fn get(&self) -> &CowVec<T> {
+        &self.vec
+    }
+
+    /// Returns a mutable reference to the wrapped `CowVec`.
+    ///
+    /// The cached hash is discarded and recomputed on the next `Hash` call.
+    pub fn get_mut(&mut self) -> &mut CowVec<T> {
+        self.hash.take();
+        &mut self.vec
+    }
+
+    /// Unwraps the `CowVec`, dropping the cached hash.
+    pub fn into_inner(self) -> CowVec<T> {
+        self.vec
+    }
+}
+
+impl<T: Hash> HashCached<T> {
+    /// Returns the cached content hash, computing it on first use.
+    fn content_hash(&self) -> u64 {
+        *self.hash.get_or_init(|| {
+            // `DefaultHasher::new()` uses fixed keys, so equal contents always
+            // produce the same cached value.
+            let mut hasher = DefaultHasher::new();
+            self.vec.hash(&mut hasher);
+            hasher.finish()
+        })
+    }
+}
+
+impl<T> Deref for HashCached<T> {
+    type Target = CowVec<T>;
+
+    fn deref(&self) -> &CowVec<T> {
+        &self.vec
+    }
+}
+
+impl<T> Clone for HashCached<T> {
+    /// Clones the wrapped `CowVec` in O(1) and copies any cached hash into a
+    /// cache owned by the clone.
+    fn clone(&self) -> Self {
+        Self {
+            vec: self.vec.clone(),
+            hash: self.hash.clone(),
+        }
+    }
+}
+
+impl<T> From<CowVec<T>> for HashCached<T> {
+    fn from(vec: CowVec<T>) -> Self {
+        Self::new(vec)
+    }
+}
+
+impl<T: Hash> Hash for HashCached<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.content_hash());
+    }
+}
+
+impl<T: PartialEq> PartialEq for HashCached<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+impl<T: Eq> Eq for HashCached<T> {}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for HashCached<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.vec.fmt(f)
+    }
+}
@@ -4,6 +4,8 @@ use super::CowVec;
 pub struct CowVecIter<'a, T> {
     pub(super) vec: &'a CowVec<T>,
     pub(super) position: usize,
+    /// One past the last index still to be yielded from the back.
+    pub(super) end: usize,
 }
 
 impl<'a, T> Iterator for CowVecIter<'a, T> {
@@ -11,7 +13,7 @@ impl<'a, T> Iterator for CowVecIter<'a, T> {
 
     /// Advances the iterator and returns the next element.
     fn next(&mut self) -> Option<Self::Item> {
-        if self.position < self.vec.len() {
+        if self.position < self.end {
             let item = self.vec.get(self.position);
             self.position += 1;
             item
@@ -22,9 +24,41 @@ impl<'a, T> Iterator for CowVecIter<'a, T> {
 
     /// Returns the bounds on the remaining length of the iterator.
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.vec.len() - self.position;
+        let remaining = self.end - self.position;
         (remaining, Some(remaining))
     }
+
+    /// Returns the `n`th remaining element in O(1) by advancing the position directly.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.position = self.position.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    /// Returns the final element in O(1), consuming the iterator.
+    fn last(self) -> Option<Self::Item> {
+        if self.position < self.end {
+            self.vec.get(self.end - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of remaining elements in O(1), consuming the iterator.
+    fn count(self) -> usize {
+        self.end - self.position
+    }
+}
+
+impl<T> DoubleEndedIterator for CowVecIter<'_, T> {
+    /// Returns the next element from the back.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.position < self.end {
+            self.end -= 1;
+            self.vec.get(self.end)
+        } else {
+            None
+        }
+    }
 }
 
 impl<T> ExactSizeIterator for CowVecIter<'_, T> {}
@@ -38,3 +72,181 @@ impl<'a, T> IntoIterator for &'a CowVec<T> {
         self.iter()
     }
 }
+
+/// An iterator over runs of consecutive elements of a `CowVec` that satisfy a predicate.
+///
+/// Created by [`CowVec::chunk_by`].
+pub struct ChunkBy<'a, T, F> {
+    pub(super) slice: &'a [&'a T],
+    pub(super) predicate: F,
+}
+
+impl<'a, T, F> Iterator for ChunkBy<'a, T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = &'a [&'a T];
+
+    /// Returns the next run, extending it while adjacent pairs satisfy the predicate.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.is_empty() {
+            return None;
+        }
+        let mut len = 1;
+        while len < self.slice.len() && (self.predicate)(self.slice[len - 1], self.slice[len]) {
+            len += 1;
+        }
+        let (run, rest) = self.slice.split_at(len);
+        self.slice = rest;
+        Some(run)
+    }
+
+    /// Returns the bounds on the number of remaining runs.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.slice.is_empty() {
+            (0, Some(0))
+        } else {
+            (1, Some(self.slice.len()))
+        }
+    }
+}
+
+/// An iterator over segments of a `CowVec` separated by elements matching a
+/// predicate, starting from the front.
+///
+/// Created by [`CowVec::split`].
+pub struct Split<'a, T, F> {
+    pub(super) slice: Option<&'a [&'a T]>,
+    pub(super) predicate: F,
+}
+
+impl<'a, T, F> Iterator for Split<'a, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = &'a [&'a T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.slice?;
+        match slice.iter().position(|item| (self.predicate)(item)) {
+            Some(index) => {
+                self.slice = Some(&slice[index + 1..]);
+                Some(&slice[..index])
+            }
+            None => {
+                self.slice = None;
+                Some(slice)
+            }
+        }
+    }
+}
+
+/// An iterator over segments of a `CowVec` separated by elements matching a
+/// predicate, starting from the back.
+///
+/// Created by [`CowVec::rsplit`].
+pub struct RSplit<'a, T, F> {
+    pub(super) slice: Option<&'a [&'a T]>,
+    pub(super) predicate: F,
+}
+
+impl<'a, T, F> Iterator for RSplit<'a, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = &'a [&'a T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.slice?;
+        match slice.iter().rposition(|item| (self.predicate)(item)) {
+            Some(index) => {
+                self.slice = Some(&slice[..index]);
+                Some(&slice[index + 1..])
+            }
+            None => {
+                self.slice = None;
+                Some(slice)
+            }
+        }
+    }
+}
+
+/// An iterator over at most `n` segments of a `CowVec` separated by elements
+/// matching a predicate, starting from the front.
+///
+/// Created by [`CowVec::splitn`].
+pub struct SplitN<'a, T, F> {
+    pub(super) slice: Option<&'a [&'a T]>,
+    pub(super) remaining: usize,
+    pub(super) predicate: F,
+}
+
+impl<'a, T, F> Iterator for SplitN<'a, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = &'a [&'a T];
+
+    /// Returns the next segment; the last segment holds everything that is left.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let slice = self.slice?;
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.slice = None;
+            return Some(slice);
+        }
+        match slice.iter().position(|item| (self.predicate)(item)) {
+            Some(index) => {
+                self.slice = Some(&slice[index + 1..]);
+                Some(&slice[..index])
+            }
+            None => {
+                self.slice = None;
+                Some(slice)
+            }
+        }
+    }
+}
+
+/// An iterator over at most `n` segments of a `CowVec` separated by elements
+/// matching a predicate, starting from the back.
+///
+/// Created by [`CowVec::rsplitn`].
+pub struct RSplitN<'a, T, F> {
+    pub(super) slice: Option<&'a [&'a T]>,
+    pub(super) remaining: usize,
+    pub(super) predicate: F,
+}
+
+impl<'a, T, F> Iterator for RSplitN<'a, T, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = &'a [&'a T];
+
+    /// Returns the next segment from the back; the last segment holds everything that is left.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let slice = self.slice?;
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.slice = None;
+            return Some(slice);
+        }
+        match slice.iter().rposition(|item| (self.predicate)(item)) {
+            Some(index) => {
+                self.slice = Some(&slice[..index]);
+                Some(&slice[index + 1..])
+            }
+            None => {
+                self.slice = None;
+                Some(slice)
+            }
+        }
+    }
+}
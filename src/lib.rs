@@ -1,10 +1,30 @@
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod builder;
 mod cow_vec;
+mod entry;
+mod hash_cached;
 mod iterator;
+#[cfg(feature = "rayon")]
+mod par_iter;
+#[cfg(feature = "arc-swap")]
+mod shared;
+#[cfg(feature = "proptest")]
+mod strategy;
 
-pub use cow_vec::CowVec;
-pub use iterator::CowVecIter;
+pub use builder::{CompactingCowVec, CowVecBuilder};
+pub use cow_vec::{ArenaStats, CowVec, ElemHandle};
+pub use entry::Entry;
+pub use hash_cached::HashCached;
+pub use iterator::{ChunkBy, CowVecIter, RSplit, RSplitN, Split, SplitN};
+#[cfg(feature = "rayon")]
+pub use par_iter::CowVecParIter;
+#[cfg(feature = "arc-swap")]
+pub use shared::SharedCowVec;
+#[cfg(feature = "proptest")]
+pub use strategy::cow_vec_strategy;
 
 #[cfg(test)]
 #[path = "tests/cow_vec_tests.rs"]
@@ -0,0 +1,90 @@
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{Copied, IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::prelude::*;
+
+use super::CowVec;
+
+/// A parallel iterator over the elements of a `CowVec`.
+///
+/// Splitting happens on the pointer vector; each leaf only dereferences
+/// pointers into the arena, which is never mutated by reads.
+pub struct CowVecParIter<'a, T: Sync> {
+    inner: Copied<rayon::slice::Iter<'a, &'a T>>,
+}
+
+impl<'a, T: Sync> ParallelIterator for CowVecParIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.inner.drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.inner.opt_len()
+    }
+}
+
+impl<T: Sync> IndexedParallelIterator for CowVecParIter<'_, T> {
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        self.inner.drive(consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        self.inner.with_producer(callback)
+    }
+}
+
+impl<'a, T: Sync> IntoParallelIterator for &'a CowVec<T> {
+    type Item = &'a T;
+    type Iter = CowVecParIter<'a, T>;
+
+    /// Creates a parallel iterator over references to the elements.
+    fn into_par_iter(self) -> Self::Iter {
+        CowVecParIter {
+            inner: self.as_slice().par_iter().copied(),
+        }
+    }
+}
+
+impl<T: Sync> CowVec<T> {
+    /// Calls `f` on each `chunk_size`-element chunk of the vector, processing
+    /// chunks in parallel on the rayon thread pool.
+    ///
+    /// Chunks are taken from [`as_slice`](CowVec::as_slice), so no elements are
+    /// cloned; the last chunk may be shorter than `chunk_size`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use cow_vec::CowVec;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// let vec = CowVec::from((0..10).collect::<Vec<_>>());
+    /// let total = AtomicUsize::new(0);
+    /// vec.par_for_each_chunk(3, |chunk| {
+    ///     total.fetch_add(chunk.iter().copied().sum::<usize>(), Ordering::Relaxed);
+    /// });
+    /// assert_eq!(total.into_inner(), 45);
+    /// ```
+    pub fn par_for_each_chunk<F>(&self, chunk_size: usize, f: F)
+    where
+        F: Fn(&[&T]) + Sync,
+    {
+        self.as_slice().par_chunks(chunk_size).for_each(&f);
+    }
+}
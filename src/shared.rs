@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+
+use super::CowVec;
+
+/// A `CowVec` that many threads can read while writers publish new versions.
+///
+/// A plain `CowVec` only ever reflects its own instance's mutations: one
+/// thread cannot observe another thread growing its clone. `SharedCowVec`
+/// keeps the current version behind an [`ArcSwap`], so readers take cheap,
+/// lock-free snapshots and writers replace the whole version atomically.
+/// A snapshot is always a complete version, never a partially applied update.
+///
+/// Updates copy the current version in O(1), apply the edit with the usual
+/// copy-on-write, and publish the result. Concurrent writers are retried
+/// rather than lost, but a single-writer/many-reader pattern avoids repeating
+/// work.
+///
+/// # Example
+/// ```
+/// use cow_vec::{CowVec, SharedCowVec};
+///
+/// let shared = SharedCowVec::new(CowVec::from(vec![1, 2]));
+/// let before = shared.snapshot();
+/// shared.update(|vec| vec.push(3));
+/// assert_eq!(before.to_vec(), vec![1, 2]);
+/// assert_eq!(shared.snapshot().to_vec(), vec![1, 2, 3]);
+/// ```
+pub struct SharedCowVec<T> {
+    current: ArcSwap<CowVec<T>>,
+}
+
+impl<T> SharedCowVec<T> {
+    /// Wraps `vec` as the initial published version.
+    pub fn new(vec: CowVec<T>) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(vec),
+        }
+    }
+
+    /// Returns an O(1) clone of the current version.
+    ///
+    /// The snapshot is unaffected by later updates.
+    pub fn snapshot(&self) -> CowVec<T> {
+        CowVec::clone(&self.current.load())
+    }
+
+    /// Replaces the current version with `vec`.
+    pub fn store(&self, vec: CowVec<T>) {
+        self.current.store(Arc::new(vec));
+    }
+
+    /// Applies `f` to a copy of the current version and publishes the result.
+    ///
+    /// If another writer publishes first, `f` runs again on the newer version,
+    /// so it may be called more than once.
+    pub fn update<F>(&self, mut f: F)
+    where
+        F: FnMut(&mut CowVec<T>),
+    {
+        self.current.rcu(|current| {
+            let mut next = CowVec::clone(current);
+            f(&mut next);
+            next
+        });
+    }
+
+    /// Unwraps the current version.
+    pub fn into_inner(self) -> CowVec<T> {
+        Arc::unwrap_or_clone(self.current.into_inner())
+    }
+}
+
+impl<T> From<CowVec<T>> for SharedCowVec<T> {
+    fn from(vec: CowVec<T>) -> Self {
+        Self::new(vec)
+    }
+}
@@ -0,0 +1,28 @@
+use proptest::collection::{vec, SizeRange};
+use proptest::strategy::Strategy;
+
+use super::CowVec;
+
+/// Returns a proptest `Strategy` generating `CowVec`s with a fresh arena.
+///
+/// Elements are drawn from `element` and the length from `size`. Shrinking
+/// follows `proptest::collection::vec`: shorter vectors and simpler elements.
+///
+/// # Example
+/// ```ignore
+/// use cow_vec::cow_vec_strategy;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     #[test]
+///     fn clone_is_equal(vec in cow_vec_strategy(any::<i32>(), 0..100)) {
+///         prop_assert_eq!(vec.clone().to_vec(), vec.to_vec());
+///     }
+/// }
+/// ```
+pub fn cow_vec_strategy<T: Strategy>(
+    element: T,
+    size: impl Into<SizeRange>,
+) -> impl Strategy<Value = CowVec<T::Value>> {
+    vec(element, size).prop_map(CowVec::from)
+}
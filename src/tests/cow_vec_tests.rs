@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 use std::thread;
 
@@ -1027,3 +1028,3338 @@ fn test_sharing_with_multiple_clones() {
     assert!(vec2.is_storage_shared());
     assert!(vec3.is_storage_shared());
 }
+
+// ============================================================================
+// Parallel iteration tests
+// ============================================================================
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_iter_sum_matches_sequential() {
+    use rayon::prelude::*;
+
+    let vec = CowVec::from((0..100_000u64).collect::<Vec<_>>());
+    let parallel: u64 = vec.par_iter().sum();
+    let sequential: u64 = vec.iter().sum();
+    assert_eq!(parallel, sequential);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_iter_preserves_order() {
+    use rayon::prelude::*;
+
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let collected: Vec<i32> = vec.par_iter().map(|&x| x * 10).collect();
+    assert_eq!(collected, vec![10, 20, 30, 40, 50]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_for_each_chunk_visits_each_element_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let vec = CowVec::from((0..1_000usize).collect::<Vec<_>>());
+    let visits: Vec<AtomicUsize> = (0..1_000).map(|_| AtomicUsize::new(0)).collect();
+    let chunks = AtomicUsize::new(0);
+    vec.par_for_each_chunk(64, |chunk| {
+        assert!(chunk.len() <= 64);
+        chunks.fetch_add(1, Ordering::Relaxed);
+        for &&i in chunk {
+            visits[i].fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    assert_eq!(chunks.into_inner(), 16);
+    assert!(visits.iter().all(|v| v.load(Ordering::Relaxed) == 1));
+}
+
+// ============ handle tests ============
+
+#[test]
+fn test_handle_basic() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let handle = vec.handle(1).unwrap();
+    assert_eq!(*handle.get(), 2);
+}
+
+#[test]
+fn test_handle_out_of_bounds() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert!(vec.handle(3).is_none());
+}
+
+#[test]
+fn test_handle_survives_set() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let handle = vec.handle(0).unwrap();
+    vec.set(0, 100);
+    assert_eq!(*handle.get(), 1);
+    assert_eq!(vec[0], 100);
+}
+
+#[test]
+fn test_handle_survives_push() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let handle = vec.handle(2).unwrap();
+    for i in 0..1000 {
+        vec.push(i);
+    }
+    assert_eq!(*handle.get(), 3);
+}
+
+#[test]
+fn test_handle_outlives_vec() {
+    let vec = CowVec::from(vec!["a".to_string(), "b".to_string()]);
+    let handle = vec.handle(1).unwrap();
+    drop(vec);
+    assert_eq!(handle.get(), "b");
+}
+
+// ============ Cow conversion tests ============
+
+#[test]
+fn test_to_cow() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let cow = vec.to_cow();
+    assert!(matches!(cow, Cow::Owned(_)));
+    assert_eq!(&*cow, &[1, 2, 3]);
+}
+
+#[test]
+fn test_from_cow_borrowed() {
+    let data = [1, 2, 3];
+    let vec = CowVec::from_cow(Cow::Borrowed(&data[..]));
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_from_cow_owned() {
+    let vec = CowVec::from_cow(Cow::Owned(vec!["a".to_string(), "b".to_string()]));
+    assert_eq!(vec.to_vec(), vec!["a".to_string(), "b".to_string()]);
+}
+
+// ============ From slice/array tests ============
+
+#[test]
+fn test_from_slice() {
+    let vec = CowVec::from(&[1, 2, 3][..]);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_from_slice_clones_elements() {
+    let source = vec!["a".to_string(), "b".to_string()];
+    let vec = CowVec::from(source.as_slice());
+    assert_eq!(vec.to_vec(), source);
+}
+
+#[test]
+fn test_from_array() {
+    let vec = CowVec::from([1, 2, 3]);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_from_empty_array() {
+    let vec: CowVec<i32> = CowVec::from([]);
+    assert!(vec.is_empty());
+}
+
+// ============ count_runs tests ============
+
+#[test]
+fn test_count_runs() {
+    let vec = CowVec::from(vec![1, 1, 2, 2, 2, 3]);
+    assert_eq!(vec.count_runs(), 3);
+}
+
+#[test]
+fn test_count_runs_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(vec.count_runs(), 0);
+}
+
+#[test]
+fn test_count_runs_all_equal() {
+    let vec = CowVec::from(vec![7, 7, 7, 7]);
+    assert_eq!(vec.count_runs(), 1);
+}
+
+#[test]
+fn test_count_runs_alternating() {
+    let vec = CowVec::from(vec![1, 2, 1, 2]);
+    assert_eq!(vec.count_runs(), 4);
+}
+
+// ============ rposition / rfind tests ============
+
+#[test]
+fn test_rposition_finds_last_match() {
+    let vec = CowVec::from(vec![1, 2, 3, 2, 1]);
+    assert_eq!(vec.rposition(|&x| x == 2), Some(3));
+    assert_eq!(vec.position(|&x| x == 2), Some(1));
+}
+
+#[test]
+fn test_rposition_no_match() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec.rposition(|&x| x == 10), None);
+}
+
+#[test]
+fn test_rfind_finds_last_match() {
+    let vec = CowVec::from(vec![(1, "a"), (2, "b"), (1, "c")]);
+    assert_eq!(vec.rfind(|&(k, _)| k == 1), Some(&(1, "c")));
+}
+
+#[test]
+fn test_rfind_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(vec.rfind(|_| true), None);
+}
+
+// ============ iterator nth / last / count tests ============
+
+#[test]
+fn test_iterator_nth_large() {
+    let vec = CowVec::from((0..10_000).collect::<Vec<i32>>());
+    assert_eq!(vec.iter().nth(1000), Some(&1000));
+}
+
+#[test]
+fn test_iterator_nth_advances() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let mut iter = vec.iter();
+    assert_eq!(iter.nth(1), Some(&2));
+    assert_eq!(iter.nth(1), Some(&4));
+    assert_eq!(iter.next(), Some(&5));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_iterator_nth_past_end() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let mut iter = vec.iter();
+    assert_eq!(iter.nth(10), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_iterator_last() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec.iter().last(), Some(&3));
+
+    let empty: CowVec<i32> = CowVec::new();
+    assert_eq!(empty.iter().last(), None);
+}
+
+#[test]
+fn test_iterator_last_after_exhausted() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let mut iter = vec.iter();
+    iter.nth(2);
+    assert_eq!(iter.last(), None);
+}
+
+#[test]
+fn test_iterator_count() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let mut iter = vec.iter();
+    iter.next();
+    assert_eq!(iter.count(), 3);
+}
+
+// ============ memory_usage tests ============
+
+#[test]
+fn test_memory_usage_grows_after_push() {
+    let mut vec: CowVec<u64> = CowVec::new();
+    let before = vec.memory_usage();
+    for i in 0..100 {
+        vec.push(i);
+    }
+    assert!(vec.memory_usage() > before);
+    assert!(
+        vec.memory_usage() >= 100 * (std::mem::size_of::<u64>() + std::mem::size_of::<usize>())
+    );
+}
+
+#[test]
+fn test_memory_usage_counts_arena_garbage() {
+    let mut vec = CowVec::from(vec![0u64; 10]);
+    let before = vec.memory_usage();
+    for i in 0..50 {
+        vec.set(0, i);
+    }
+    // The pointer vector is unchanged; the arena holds 50 more values.
+    assert_eq!(vec.memory_usage(), before + 50 * std::mem::size_of::<u64>());
+}
+
+// ============ shares_arena_with tests ============
+
+#[test]
+fn test_shares_arena_with_clone() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.push(4);
+    assert!(vec1.shares_arena_with(&vec2));
+    assert!(vec2.shares_arena_with(&vec1));
+    assert!(vec1.shares_arena_with(&vec1));
+}
+
+#[test]
+fn test_shares_arena_with_split_off() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4]);
+    let tail = vec.split_off(2);
+    assert!(vec.shares_arena_with(&tail));
+}
+
+#[test]
+fn test_shares_arena_with_independent() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let vec2 = CowVec::from(vec![1, 2, 3]);
+    assert!(!vec1.shares_arena_with(&vec2));
+}
+
+// ============ ptr_eq_at tests ============
+
+#[test]
+fn test_ptr_eq_at_after_set() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.set(1, 20);
+    assert!(vec1.ptr_eq_at(0, &vec2, 0));
+    assert!(!vec1.ptr_eq_at(1, &vec2, 1));
+    assert!(vec1.ptr_eq_at(2, &vec2, 2));
+}
+
+#[test]
+fn test_ptr_eq_at_different_positions() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.remove(0);
+    assert!(vec1.ptr_eq_at(1, &vec2, 0));
+    assert!(!vec1.ptr_eq_at(0, &vec2, 0));
+}
+
+#[test]
+fn test_ptr_eq_at_independent_equal_values() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let vec2 = CowVec::from(vec![1, 2, 3]);
+    assert!(!vec1.ptr_eq_at(0, &vec2, 0));
+}
+
+#[test]
+fn test_ptr_eq_at_out_of_bounds() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let vec2 = vec1.clone();
+    assert!(!vec1.ptr_eq_at(3, &vec2, 0));
+    assert!(!vec1.ptr_eq_at(0, &vec2, 3));
+}
+
+// ============ diff tests ============
+
+#[test]
+fn test_diff_after_two_sets() {
+    let base = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let mut edited = base.clone();
+    edited.set(1, 20);
+    edited.set(3, 40);
+    assert_eq!(base.diff(&edited), vec![1, 3]);
+    assert_eq!(edited.diff(&base), vec![1, 3]);
+}
+
+#[test]
+fn test_diff_identical_clone() {
+    let base = CowVec::from(vec![1, 2, 3]);
+    let clone = base.clone();
+    assert!(base.diff(&clone).is_empty());
+}
+
+#[test]
+fn test_diff_different_lengths() {
+    let base = CowVec::from(vec![1, 2, 3]);
+    let mut edited = base.clone();
+    edited.push(4);
+    edited.push(5);
+    assert_eq!(base.diff(&edited), vec![3, 4]);
+    edited.truncate(1);
+    assert_eq!(base.diff(&edited), vec![1, 2]);
+}
+
+#[test]
+fn test_diff_independent_vecs() {
+    let vec1 = CowVec::from(vec![1, 2]);
+    let vec2 = CowVec::from(vec![1, 2]);
+    assert_eq!(vec1.diff(&vec2), vec![0, 1]);
+}
+
+// ============ make_structure_unique tests ============
+
+#[test]
+fn test_make_structure_unique() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    assert!(vec2.is_structure_shared());
+
+    vec2.make_structure_unique();
+
+    assert!(!vec1.is_structure_shared());
+    assert!(!vec2.is_structure_shared());
+    assert!(vec2.is_storage_shared());
+    assert_eq!(vec2.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_make_structure_unique_when_not_shared() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.make_structure_unique();
+    assert!(!vec.is_structure_shared());
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+}
+
+// ============ batch allocation tests ============
+
+#[test]
+fn test_from_vec_million_elements() {
+    let vec = CowVec::from((0..1_000_000u64).collect::<Vec<_>>());
+    assert_eq!(vec.len(), 1_000_000);
+    assert_eq!(vec[0], 0);
+    assert_eq!(vec[999_999], 999_999);
+    assert_eq!(vec.iter().sum::<u64>(), 999_999 * 1_000_000 / 2);
+}
+
+#[test]
+fn test_batch_allocated_pointers_remain_valid() {
+    let mut vec = CowVec::from((0..1000).map(|i| i.to_string()).collect::<Vec<_>>());
+    let snapshot = vec.clone();
+    // Further batches grow the arena across new chunks.
+    for round in 0..10 {
+        vec.extend((0..1000).map(|i| format!("{}-{}", round, i)));
+    }
+    for (i, item) in snapshot.iter().enumerate() {
+        assert_eq!(item, &i.to_string());
+    }
+    assert_eq!(vec.len(), 11_000);
+    assert_eq!(vec[1000], "0-0");
+    assert_eq!(vec[10_999], "9-999");
+}
+
+#[test]
+fn test_extend_with_iterator_reading_same_arena() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    vec.extend(clone.iter().map(|&x| x * 10));
+    assert_eq!(vec.to_vec(), vec![1, 2, 3, 10, 20, 30]);
+}
+
+// ============ mutex poisoning tests ============
+
+#[test]
+fn test_poisoned_arena_still_usable() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    vec.poison_arena();
+
+    let mut clone = vec.clone();
+    let handle = thread::spawn(move || {
+        clone.push(4);
+        clone.set(0, 10);
+        clone
+    });
+    let clone = handle.join().unwrap();
+
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    assert_eq!(clone.to_vec(), vec![10, 2, 3, 4]);
+    assert_eq!(vec.clone_with_max_capacity(0).to_vec(), vec![1, 2, 3]);
+}
+
+// ============ entry tests ============
+
+#[test]
+fn test_entry_get_does_not_allocate() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let before = vec.memory_usage();
+    let entry = vec.entry(1);
+    assert_eq!(*entry.get(), 2);
+    assert_eq!(entry.index(), 1);
+    assert_eq!(vec.memory_usage(), before);
+}
+
+#[test]
+fn test_entry_modify_allocates_once() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let before = vec.memory_usage();
+    vec.entry(0).modify(|x| *x += 1);
+    assert_eq!(vec[0], 2);
+    assert_eq!(vec.memory_usage(), before + std::mem::size_of::<i32>());
+}
+
+#[test]
+fn test_entry_set() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.entry(2).set(30);
+    assert_eq!(vec.to_vec(), vec![1, 2, 30]);
+}
+
+#[test]
+fn test_entry_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.entry(0).modify(|x| *x *= 100);
+    assert_eq!(vec1.to_vec(), vec![1, 2, 3]);
+    assert_eq!(vec2.to_vec(), vec![100, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_entry_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.entry(3);
+}
+
+// ============ modify tests ============
+
+#[test]
+fn test_modify_matches_set() {
+    let mut modified = CowVec::from(vec![1, 2, 3]);
+    let mut set = modified.clone();
+    modified.modify(0, |x| *x += 5);
+    set.set(0, set[0] + 5);
+    assert_eq!(modified.to_vec(), set.to_vec());
+    assert_eq!(modified.to_vec(), vec![6, 2, 3]);
+}
+
+#[test]
+fn test_modify_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec!["a".to_string(), "b".to_string()]);
+    let mut vec2 = vec1.clone();
+    vec2.modify(1, |s| s.push('!'));
+    assert_eq!(vec1[1], "b");
+    assert_eq!(vec2[1], "b!");
+}
+
+#[test]
+fn test_modify_allocates_once() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let before = vec.memory_usage();
+    vec.modify(1, |x| {
+        *x += 1;
+        *x *= 2;
+        *x -= 1;
+    });
+    assert_eq!(vec[1], 5);
+    assert_eq!(vec.memory_usage(), before + std::mem::size_of::<i32>());
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_modify_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.modify(3, |x| *x += 1);
+}
+
+// ============ replace tests ============
+
+#[test]
+fn test_replace_returns_previous_value() {
+    let mut vec = CowVec::from(vec!["a".to_string(), "b".to_string()]);
+    let old = vec.replace(1, "c".to_string()).clone();
+    assert_eq!(old, "b");
+    assert_eq!(vec.to_vec(), vec!["a".to_string(), "c".to_string()]);
+}
+
+#[test]
+fn test_replace_undo() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let old = *vec.replace(2, 30);
+    assert_eq!(vec[2], 30);
+    vec.set(2, old);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_replace_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    assert_eq!(*vec2.replace(0, 10), 1);
+    assert_eq!(vec1[0], 1);
+    assert_eq!(vec2[0], 10);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_replace_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.replace(3, 10);
+}
+
+// ============ pop_owned / remove_owned tests ============
+
+#[test]
+fn test_pop_owned() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec.pop_owned(), Some(3));
+    assert_eq!(vec.to_vec(), vec![1, 2]);
+
+    let mut empty: CowVec<i32> = CowVec::new();
+    assert_eq!(empty.pop_owned(), None);
+}
+
+#[test]
+fn test_remove_owned() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec.remove_owned(1), 2);
+    assert_eq!(vec.to_vec(), vec![1, 3]);
+}
+
+#[test]
+fn test_owned_values_independent_of_later_mutations() {
+    let mut vec = CowVec::from(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    let mut popped = vec.pop_owned().unwrap();
+    let removed = vec.remove_owned(0);
+    vec.push("d".to_string());
+    vec.set(0, "e".to_string());
+    popped.push('!');
+    assert_eq!(popped, "c!");
+    assert_eq!(removed, "a");
+    assert_eq!(vec.to_vec(), vec!["e".to_string(), "d".to_string()]);
+}
+
+#[test]
+#[should_panic]
+fn test_remove_owned_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.remove_owned(3);
+}
+
+#[test]
+fn test_swap_remove_owned_moves_last_into_slot() {
+    let mut vec = CowVec::from(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    let clone = vec.clone();
+    let mut removed = vec.swap_remove_owned(0);
+    assert_eq!(vec.to_vec(), vec!["c".to_string(), "b".to_string()]);
+    vec.set(0, "x".to_string());
+    vec.push("y".to_string());
+    removed.push('!');
+    assert_eq!(removed, "a!");
+    assert_eq!(clone.to_vec(), vec!["a", "b", "c"]);
+    assert_eq!(vec.swap_remove_owned(2), "y");
+}
+
+#[test]
+#[should_panic]
+fn test_swap_remove_owned_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.swap_remove_owned(3);
+}
+
+// ============ subvec tests ============
+
+#[test]
+fn test_subvec_basic() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    assert_eq!(vec.subvec(1..3).to_vec(), vec![2, 3]);
+    assert_eq!(vec.subvec(..2).to_vec(), vec![1, 2]);
+    assert_eq!(vec.subvec(3..).to_vec(), vec![4, 5]);
+    assert_eq!(vec.subvec(1..=3).to_vec(), vec![2, 3, 4]);
+    assert!(vec.subvec(2..2).is_empty());
+}
+
+#[test]
+fn test_subvec_shares_arena() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let sub = vec.subvec(1..3);
+    assert!(sub.shares_arena_with(&vec));
+    assert!(sub.ptr_eq_at(0, &vec, 1));
+}
+
+#[test]
+fn test_subvec_set_does_not_affect_parent() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let mut sub = vec.subvec(1..3);
+    sub.set(0, 20);
+    sub.push(30);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3, 4, 5]);
+    assert_eq!(sub.to_vec(), vec![20, 3, 30]);
+}
+
+#[test]
+#[should_panic]
+fn test_subvec_out_of_bounds() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    vec.subvec(1..4);
+}
+
+// ============ Display tests ============
+
+#[test]
+fn test_display_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(format!("{}", vec), "[]");
+}
+
+#[test]
+fn test_display_single_element() {
+    let vec = CowVec::from(vec![42]);
+    assert_eq!(format!("{}", vec), "[42]");
+}
+
+#[test]
+fn test_display_multiple_elements() {
+    let vec = CowVec::from(vec!["hello".to_string(), "world".to_string()]);
+    assert_eq!(format!("{}", vec), "[hello, world]");
+}
+
+#[test]
+fn test_display_forwards_element_format() {
+    let vec = CowVec::from(vec![1.5, 2.25]);
+    assert_eq!(format!("{:.1}", vec), "[1.5, 2.2]");
+}
+
+// ============ PartialEq against Vec and slices ============
+
+#[test]
+fn test_eq_vec() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec, vec![1, 2, 3]);
+    assert_eq!(vec![1, 2, 3], vec);
+    assert_ne!(vec, vec![1, 2, 4]);
+}
+
+#[test]
+fn test_eq_slice() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec, &[1, 2, 3][..]);
+    assert_eq!(&[1, 2, 3][..], vec);
+    assert!(vec == *[1, 2, 3].as_slice());
+    assert!(*[1, 2, 3].as_slice() == vec);
+}
+
+#[test]
+fn test_eq_length_mismatch() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_ne!(vec, vec![1, 2]);
+    assert_ne!(vec, vec![1, 2, 3, 4]);
+    assert_ne!(vec, &[][..]);
+}
+
+#[test]
+fn test_eq_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(vec, Vec::<i32>::new());
+}
+
+// ============ boxed slice tests ============
+
+#[test]
+fn test_to_boxed_slice() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let boxed = vec.to_boxed_slice();
+    vec.set(0, 10);
+    vec.push(4);
+    assert_eq!(&*boxed, &[1, 2, 3]);
+}
+
+#[test]
+fn test_into_boxed_slice_unique_arena_moves_values() {
+    #[derive(Debug, PartialEq)]
+    struct NoCloneCalls(i32);
+    impl Clone for NoCloneCalls {
+        fn clone(&self) -> Self {
+            panic!("unexpected clone");
+        }
+    }
+
+    let mut vec = CowVec::from(vec![NoCloneCalls(1), NoCloneCalls(2), NoCloneCalls(3)]);
+    vec.swap(0, 2);
+    vec.set(1, NoCloneCalls(20));
+    vec.push(NoCloneCalls(4));
+    let boxed = vec.into_boxed_slice();
+    assert_eq!(
+        &*boxed,
+        &[
+            NoCloneCalls(3),
+            NoCloneCalls(20),
+            NoCloneCalls(1),
+            NoCloneCalls(4)
+        ]
+    );
+}
+
+#[test]
+fn test_into_boxed_slice_shared_arena() {
+    let vec1 = CowVec::from(vec!["a".to_string(), "b".to_string()]);
+    let mut vec2 = vec1.clone();
+    vec2.set(0, "c".to_string());
+    let boxed = vec2.into_boxed_slice();
+    assert_eq!(&*boxed, &["c".to_string(), "b".to_string()]);
+    assert_eq!(vec1.to_vec(), vec!["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn test_into_boxed_slice_empty() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.clear();
+    assert!(vec.into_boxed_slice().is_empty());
+}
+
+#[test]
+fn test_into_boxed_slice_zero_sized() {
+    let vec = CowVec::from(vec![(), ()]);
+    assert_eq!(vec.into_boxed_slice().len(), 2);
+
+    let mut vec = CowVec::from(vec![(), (), ()]);
+    vec.pop();
+    assert_eq!(vec.into_boxed_slice().len(), 2);
+
+    let mut vec = CowVec::from(vec![(), ()]);
+    let other = vec.clone();
+    vec.push_from(&other, 0);
+    vec.push_from(&other, 1);
+    drop(other);
+    assert_eq!(vec.into_boxed_slice().len(), 4);
+}
+
+// ============ Arbitrary tests ============
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary_from_fixed_bytes() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let bytes: Vec<u8> = (0..=255).collect();
+    let mut u = Unstructured::new(&bytes);
+    let mut vec = CowVec::<u16>::arbitrary(&mut u).unwrap();
+
+    let clone = vec.clone();
+    let len = vec.len();
+    vec.retain(|x| x % 2 == 0);
+    vec.splice(0..vec.len().min(1), vec![1, 2]);
+    let _ = vec.split_off(vec.len() / 2);
+    assert_eq!(clone.len(), len);
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary_take_rest() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let bytes = [1u8, 0, 2, 0, 3, 0];
+    let vec = CowVec::<u16>::arbitrary_take_rest(Unstructured::new(&bytes)).unwrap();
+    assert!(vec.len() <= 3);
+}
+
+// ============ proptest tests ============
+
+#[cfg(feature = "proptest")]
+mod proptests {
+    use proptest::prelude::*;
+
+    use crate::cow_vec_strategy;
+
+    proptest! {
+        #[test]
+        fn split_off_and_extend_round_trips(
+            vec in cow_vec_strategy(any::<i32>(), 0..64),
+            at in any::<prop::sample::Index>(),
+        ) {
+            let original = vec.to_vec();
+            let mut head = vec.clone();
+            let tail = head.split_off(at.index(vec.len() + 1));
+            head.extend(tail.iter().copied());
+            prop_assert_eq!(head.to_vec(), original);
+        }
+
+        #[test]
+        fn sets_on_clone_never_affect_source(
+            vec in cow_vec_strategy(any::<i32>(), 1..64),
+            edits in prop::collection::vec((any::<prop::sample::Index>(), any::<i32>()), 0..32),
+        ) {
+            let original = vec.to_vec();
+            let mut clone = vec.clone();
+            for (index, value) in edits {
+                clone.set(index.index(clone.len()), value);
+            }
+            prop_assert_eq!(vec.to_vec(), original);
+        }
+    }
+}
+
+// ============ unchecked access tests ============
+
+#[test]
+fn test_get_unchecked() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let sum: i32 = (0..vec.len())
+        .map(|i| unsafe { *vec.get_unchecked(i) })
+        .sum();
+    assert_eq!(sum, 6);
+}
+
+#[test]
+fn test_set_unchecked() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    for i in 0..vec2.len() {
+        unsafe { vec2.set_unchecked(i, (i as i32 + 1) * 10) };
+    }
+    assert_eq!(vec1.to_vec(), vec![1, 2, 3]);
+    assert_eq!(vec2.to_vec(), vec![10, 20, 30]);
+}
+
+// ============ array_windows / array_chunks tests ============
+
+#[test]
+fn test_array_chunks_even() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let chunks: Vec<[&i32; 2]> = vec.array_chunks::<2>().collect();
+    assert_eq!(chunks, vec![[&1, &2], [&3, &4]]);
+}
+
+#[test]
+fn test_array_chunks_drops_remainder() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let chunks: Vec<[&i32; 2]> = vec.array_chunks::<2>().collect();
+    assert_eq!(chunks, vec![[&1, &2], [&3, &4]]);
+}
+
+#[test]
+fn test_array_windows() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let windows: Vec<[&i32; 3]> = vec.array_windows::<3>().collect();
+    assert_eq!(windows, vec![[&1, &2, &3], [&2, &3, &4]]);
+}
+
+#[test]
+fn test_array_windows_shorter_than_n() {
+    let vec = CowVec::from(vec![1, 2]);
+    assert_eq!(vec.array_windows::<3>().count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_array_chunks_zero() {
+    let vec = CowVec::from(vec![1, 2]);
+    let _ = vec.array_chunks::<0>().count();
+}
+
+// ============ first_chunk / last_chunk tests ============
+
+#[test]
+fn test_first_chunk() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    assert_eq!(vec.first_chunk::<3>(), Some([&1, &2, &3]));
+    assert_eq!(vec.first_chunk::<4>(), Some([&1, &2, &3, &4]));
+}
+
+#[test]
+fn test_first_chunk_too_short() {
+    let vec = CowVec::from(vec![1, 2]);
+    assert_eq!(vec.first_chunk::<3>(), None);
+}
+
+#[test]
+fn test_last_chunk() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    assert_eq!(vec.last_chunk::<3>(), Some([&2, &3, &4]));
+    assert_eq!(vec.last_chunk::<5>(), None);
+}
+
+#[test]
+fn test_chunk_zero_length() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(vec.first_chunk::<0>(), Some([]));
+    assert_eq!(vec.last_chunk::<0>(), Some([]));
+}
+
+// ============ deep_clone tests ============
+
+#[test]
+fn test_deep_clone_does_not_share() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    for i in 0..100 {
+        vec.set(0, i);
+    }
+    let copy = vec.deep_clone();
+    assert!(!copy.shares_arena_with(&vec));
+    assert!(!copy.is_storage_shared());
+    assert!(!copy.is_structure_shared());
+    assert_eq!(copy.to_vec(), vec.to_vec());
+    assert!(copy.memory_usage() < vec.memory_usage());
+}
+
+#[test]
+fn test_deep_clone_independent() {
+    let vec1 = CowVec::from(vec!["a".to_string(), "b".to_string()]);
+    let mut vec2 = vec1.deep_clone();
+    vec2.set(0, "c".to_string());
+    assert_eq!(vec1[0], "a");
+    assert_eq!(vec2[0], "c");
+}
+
+// ============ is_compact tests ============
+
+#[test]
+fn test_is_compact_fresh() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert!(vec.is_compact());
+    let empty: CowVec<i32> = CowVec::new();
+    assert!(empty.is_compact());
+}
+
+#[test]
+fn test_is_compact_after_pop() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.pop();
+    assert!(!vec.is_compact());
+}
+
+#[test]
+fn test_is_compact_after_set() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.set(0, 10);
+    assert!(!vec.is_compact());
+    assert!(vec.deep_clone().is_compact());
+}
+
+#[test]
+fn test_is_compact_after_push() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.push(4);
+    assert!(vec.is_compact());
+}
+
+// ============ in_arena_of tests ============
+
+#[test]
+fn test_in_arena_of_shares_arena() {
+    let base = CowVec::from(vec![1, 2, 3]);
+    let related = CowVec::in_arena_of(&base);
+    assert!(related.is_empty());
+    assert!(related.shares_arena_with(&base));
+    assert!(base.is_storage_shared());
+    assert!(!related.is_structure_shared());
+}
+
+#[test]
+fn test_in_arena_of_push_does_not_affect_other() {
+    let base = CowVec::from(vec![1, 2, 3]);
+    let mut related = CowVec::in_arena_of(&base);
+    related.push(4);
+    related.push(5);
+    assert_eq!(base.to_vec(), vec![1, 2, 3]);
+    assert_eq!(related.to_vec(), vec![4, 5]);
+    assert!(!base.is_compact());
+}
+
+// ============ copy_within tests ============
+
+#[test]
+fn test_copy_within_overlapping_forward() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    vec.copy_within(0..3, 2);
+    assert_eq!(vec.to_vec(), vec![1, 2, 1, 2, 3]);
+}
+
+#[test]
+fn test_copy_within_overlapping_backward() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    vec.copy_within(2.., 0);
+    assert_eq!(vec.to_vec(), vec![3, 4, 5, 4, 5]);
+}
+
+#[test]
+fn test_copy_within_shares_pointers() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4]);
+    let before = vec.memory_usage();
+    vec.copy_within(0..2, 2);
+    assert_eq!(vec.memory_usage(), before);
+    assert!(vec.ptr_eq_at(0, &vec, 2));
+}
+
+#[test]
+fn test_copy_within_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.copy_within(..1, 2);
+    assert_eq!(vec1.to_vec(), vec![1, 2, 3]);
+    assert_eq!(vec2.to_vec(), vec![1, 2, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_copy_within_dest_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.copy_within(0..2, 2);
+}
+
+// ============ clone_from tests ============
+
+#[test]
+fn test_clone_from_contents_and_arena() {
+    let src = CowVec::from(vec![1, 2, 3]);
+    let mut dst = CowVec::from(vec![7, 8]);
+    dst.clone_from(&src);
+    assert_eq!(dst.to_vec(), vec![1, 2, 3]);
+    assert!(dst.shares_arena_with(&src));
+}
+
+#[test]
+fn test_clone_from_reuses_unique_structure() {
+    let src = CowVec::from(vec![1, 2, 3]);
+    let mut dst: CowVec<i32> = CowVec::with_capacity(16);
+    dst.clone_from(&src);
+    // The destination kept its own pointer vector rather than sharing src's.
+    assert!(!dst.is_structure_shared());
+    assert!(!src.is_structure_shared());
+}
+
+#[test]
+fn test_clone_from_shared_destination() {
+    let src = CowVec::from(vec![1, 2, 3]);
+    let other = CowVec::from(vec![4, 5]);
+    let mut dst = other.clone();
+    dst.clone_from(&src);
+    assert_eq!(dst.to_vec(), vec![1, 2, 3]);
+    assert_eq!(other.to_vec(), vec![4, 5]);
+}
+
+#[test]
+fn test_clone_from_isolation() {
+    let src = CowVec::from(vec![1, 2, 3]);
+    let mut dst = CowVec::new();
+    for round in 0..3 {
+        dst.clone_from(&src);
+        dst.set(0, round);
+        assert_eq!(src.to_vec(), vec![1, 2, 3]);
+        assert_eq!(dst.to_vec(), vec![round, 2, 3]);
+    }
+}
+
+// ============ partition tests ============
+
+#[test]
+fn test_partition_by_parity() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let (even, odd) = vec.partition(|&x| x % 2 == 0);
+    assert_eq!(even.to_vec(), vec![2, 4]);
+    assert_eq!(odd.to_vec(), vec![1, 3, 5]);
+    assert!(even.shares_arena_with(&vec));
+    assert!(odd.shares_arena_with(&vec));
+    assert!(even.ptr_eq_at(0, &vec, 1));
+}
+
+#[test]
+fn test_partition_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    let (a, b) = vec.partition(|_| true);
+    assert!(a.is_empty());
+    assert!(b.is_empty());
+}
+
+#[test]
+fn test_partition_results_independent() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let (mut small, _) = vec.partition(|&x| x < 3);
+    small.set(0, 10);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3, 4]);
+    assert_eq!(small.to_vec(), vec![10, 2]);
+}
+
+// ============ chunk_by tests ============
+
+#[test]
+fn test_chunk_by_equality() {
+    let vec = CowVec::from(vec![1, 1, 2, 3, 3, 3]);
+    let runs: Vec<Vec<i32>> = vec
+        .chunk_by(|a, b| a == b)
+        .map(|run| run.iter().map(|&&x| x).collect())
+        .collect();
+    assert_eq!(runs, vec![vec![1, 1], vec![2], vec![3, 3, 3]]);
+}
+
+#[test]
+fn test_chunk_by_ascending() {
+    let vec = CowVec::from(vec![1, 2, 3, 1, 2, 0]);
+    let lens: Vec<usize> = vec.chunk_by(|a, b| a < b).map(|run| run.len()).collect();
+    assert_eq!(lens, vec![3, 2, 1]);
+}
+
+#[test]
+fn test_chunk_by_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(vec.chunk_by(|a, b| a == b).count(), 0);
+}
+
+// ============ partition_point / select_nth_unstable tests ============
+
+#[test]
+fn test_partition_point_sorted() {
+    let vec = CowVec::from(vec![1, 2, 3, 3, 5, 6, 7]);
+    assert_eq!(vec.partition_point(|&x| x < 3), 2);
+    assert_eq!(vec.partition_point(|&x| x <= 3), 4);
+    assert_eq!(vec.partition_point(|_| true), 7);
+    assert_eq!(vec.partition_point(|_| false), 0);
+}
+
+#[test]
+fn test_select_nth_unstable_median() {
+    let mut vec = CowVec::from(vec![9, 1, 8, 2, 7, 3, 6, 4, 5]);
+    let median = *vec.select_nth_unstable(4);
+    assert_eq!(median, 5);
+    assert_eq!(vec[4], 5);
+    assert!(vec.iter().take(4).all(|&x| x <= 5));
+    assert!(vec.iter().skip(5).all(|&x| x >= 5));
+}
+
+#[test]
+fn test_select_nth_unstable_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec![3, 1, 2]);
+    let mut vec2 = vec1.clone();
+    vec2.select_nth_unstable(0);
+    assert_eq!(vec1.to_vec(), vec![3, 1, 2]);
+    assert_eq!(vec2[0], 1);
+}
+
+#[test]
+#[should_panic]
+fn test_select_nth_unstable_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.select_nth_unstable(3);
+}
+
+// ============ rand tests ============
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_shuffle_deterministic_with_seed() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let vec = CowVec::from((0..50).collect::<Vec<i32>>());
+    let mut a = vec.clone();
+    let mut b = vec.clone();
+    a.shuffle(&mut StdRng::seed_from_u64(42));
+    b.shuffle(&mut StdRng::seed_from_u64(42));
+    assert_eq!(a.to_vec(), b.to_vec());
+    assert_ne!(a.to_vec(), vec.to_vec());
+
+    let mut sorted = a.to_vec();
+    sorted.sort();
+    assert_eq!(sorted, vec.to_vec());
+    assert_eq!(vec.to_vec(), (0..50).collect::<Vec<i32>>());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_shuffle_does_not_allocate() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut vec = CowVec::from((0..10).collect::<Vec<i32>>());
+    let before = vec.memory_usage();
+    vec.shuffle(&mut StdRng::seed_from_u64(1));
+    assert_eq!(vec.memory_usage(), before);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_choose() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let vec = CowVec::from(vec![1, 2, 3]);
+    for _ in 0..20 {
+        assert!(vec.contains(vec.choose(&mut rng).unwrap()));
+    }
+    let empty: CowVec<i32> = CowVec::new();
+    assert_eq!(empty.choose(&mut rng), None);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_sample_distinct_from_source() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let vec = CowVec::from((0..100).collect::<Vec<i32>>());
+    let sample = vec.sample(&mut StdRng::seed_from_u64(3), 10);
+    assert_eq!(sample.len(), 10);
+    assert!(sample.shares_arena_with(&vec));
+
+    let mut values = sample.to_vec();
+    assert!(values.iter().all(|x| vec.contains(x)));
+    values.sort();
+    values.dedup();
+    assert_eq!(values.len(), 10);
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_sample_k_exceeds_len() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let sample = vec.sample(&mut StdRng::seed_from_u64(5), 10);
+    let mut values = sample.to_vec();
+    values.sort();
+    assert_eq!(values, vec![1, 2, 3, 4]);
+}
+
+// ============ is_sorted tests ============
+
+#[test]
+fn test_is_sorted_ascending() {
+    assert!(CowVec::from(vec![1, 2, 2, 3]).is_sorted());
+    assert!(!CowVec::from(vec![1, 3, 2]).is_sorted());
+}
+
+#[test]
+fn test_is_sorted_descending() {
+    let vec = CowVec::from(vec![3, 2, 2, 1]);
+    assert!(!vec.is_sorted());
+    assert!(vec.is_sorted_by(|a, b| a >= b));
+}
+
+#[test]
+fn test_is_sorted_by_key() {
+    let vec = CowVec::from(vec![-1, 2, -3, 4]);
+    assert!(vec.is_sorted_by_key(|x: &i32| x.abs()));
+    assert!(!vec.is_sorted());
+}
+
+#[test]
+fn test_is_sorted_trivial() {
+    assert!(CowVec::<i32>::new().is_sorted());
+    assert!(CowVec::from(vec![42]).is_sorted());
+    assert!(!CowVec::from(vec![1.0, f64::NAN]).is_sorted());
+}
+
+// ============ min / max tests ============
+
+#[test]
+fn test_min_max() {
+    let vec = CowVec::from(vec![3, 1, 4, 1, 5, 9, 2, 6]);
+    assert_eq!(vec.min(), Some(&1));
+    assert_eq!(vec.max(), Some(&9));
+    assert_eq!(vec.position_min(), Some(1));
+    assert_eq!(vec.position_max(), Some(5));
+}
+
+#[test]
+fn test_min_max_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(vec.min(), None);
+    assert_eq!(vec.max(), None);
+    assert_eq!(vec.position_min(), None);
+    assert_eq!(vec.position_max(), None);
+}
+
+#[test]
+fn test_min_max_ties() {
+    // Ordered by key only, so equal keys are distinguishable by tag.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Tagged(i32, &'static str);
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let vec = CowVec::from(vec![
+        Tagged(2, "a"),
+        Tagged(1, "b"),
+        Tagged(2, "c"),
+        Tagged(1, "d"),
+    ]);
+    assert_eq!(vec.min(), Some(&Tagged(1, "b")));
+    assert_eq!(vec.max(), Some(&Tagged(2, "c")));
+    assert_eq!(vec.position_min(), Some(1));
+    assert_eq!(vec.position_max(), Some(2));
+}
+
+// ============ insert_slice tests ============
+
+#[test]
+fn test_insert_slice_middle() {
+    let mut vec = CowVec::from(vec![1, 2, 5]);
+    vec.insert_slice(2, &[3, 4]);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_insert_slice_start_and_end() {
+    let mut vec = CowVec::from(vec![3]);
+    vec.insert_slice(0, &[1, 2]);
+    vec.insert_slice(3, &[4, 5]);
+    assert_eq!(vec.to_vec(), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_insert_slice_empty_values() {
+    let mut vec = CowVec::from(vec![1, 2]);
+    vec.insert_slice(1, &[]);
+    assert_eq!(vec.to_vec(), vec![1, 2]);
+}
+
+#[test]
+fn test_insert_slice_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.insert_slice(1, &[10, 20]);
+    assert_eq!(vec1.to_vec(), vec![1, 2, 3]);
+    assert_eq!(vec2.to_vec(), vec![1, 10, 20, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_insert_slice_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.insert_slice(4, &[10]);
+}
+
+// ============ extend_from_within tests ============
+
+#[test]
+fn test_extend_from_within() {
+    let mut vec = CowVec::from(vec!["a", "b", "c"]);
+    vec.extend_from_within(0..2);
+    assert_eq!(vec.to_vec(), vec!["a", "b", "c", "a", "b"]);
+}
+
+#[test]
+fn test_extend_from_within_full_range() {
+    let mut vec = CowVec::from(vec![1, 2]);
+    vec.extend_from_within(..);
+    assert_eq!(vec.to_vec(), vec![1, 2, 1, 2]);
+}
+
+#[test]
+fn test_extend_from_within_does_not_affect_clones() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.extend_from_within(1..);
+    assert_eq!(vec1.to_vec(), vec![1, 2, 3]);
+    assert_eq!(vec2.to_vec(), vec![1, 2, 3, 2, 3]);
+}
+
+#[test]
+#[should_panic]
+fn test_extend_from_within_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.extend_from_within(2..5);
+}
+
+// ============ splitn / rsplitn tests ============
+
+fn segments<'a>(iter: impl Iterator<Item = &'a [&'a i32]>) -> Vec<Vec<i32>> {
+    iter.map(|seg| seg.iter().map(|&&x| x).collect()).collect()
+}
+
+#[test]
+fn test_splitn_stops_after_first_delimiter() {
+    let vec = CowVec::from(vec![1, 0, 2, 0, 3]);
+    assert_eq!(
+        segments(vec.splitn(2, |&x| x == 0)),
+        vec![vec![1], vec![2, 0, 3]]
+    );
+}
+
+#[test]
+fn test_splitn_matches_slice() {
+    let data = [1, 0, 2, 0, 3, 0];
+    let vec: CowVec<i32> = CowVec::from(data);
+    for n in 0..6 {
+        let expected: Vec<Vec<i32>> = data.splitn(n, |&x| x == 0).map(|s| s.to_vec()).collect();
+        assert_eq!(segments(vec.splitn(n, |&x| x == 0)), expected);
+        let expected: Vec<Vec<i32>> = data.rsplitn(n, |&x| x == 0).map(|s| s.to_vec()).collect();
+        assert_eq!(segments(vec.rsplitn(n, |&x| x == 0)), expected);
+    }
+}
+
+#[test]
+fn test_rsplitn_from_back() {
+    let vec: CowVec<i32> = CowVec::from(vec![1, 0, 2, 0, 3]);
+    assert_eq!(
+        segments(vec.rsplitn(2, |&x| x == 0)),
+        vec![vec![3], vec![1, 0, 2]]
+    );
+}
+
+#[test]
+fn test_splitn_empty() {
+    let vec: CowVec<i32> = CowVec::new();
+    assert_eq!(
+        segments(vec.splitn(3, |&x| x == 0)),
+        vec![Vec::<i32>::new()]
+    );
+}
+
+// ============ zip tests ============
+
+#[test]
+fn test_zip_stops_at_shorter() {
+    let short = CowVec::from(vec![1, 2, 3]);
+    let long = CowVec::from(vec!["a", "b", "c", "d", "e"]);
+    let pairs: Vec<(&i32, &&str)> = short.zip(&long).collect();
+    assert_eq!(pairs, vec![(&1, &"a"), (&2, &"b"), (&3, &"c")]);
+    assert_eq!(long.zip(&short).count(), 3);
+}
+
+#[test]
+fn test_zip_empty() {
+    let empty: CowVec<i32> = CowVec::new();
+    let vec = CowVec::from(vec![1, 2]);
+    assert_eq!(empty.zip(&vec).count(), 0);
+}
+
+// ============ try_reclaim tests ============
+
+#[test]
+fn test_try_reclaim_sole_owner() {
+    let mut vec = CowVec::from((0..100).collect::<Vec<i32>>());
+    vec.truncate(3);
+    vec.set(0, 42);
+    assert!(!vec.is_compact());
+    let before = vec.memory_usage();
+
+    assert!(vec.try_reclaim());
+    assert!(vec.is_compact());
+    assert!(vec.memory_usage() < before);
+    assert_eq!(vec.to_vec(), vec![42, 1, 2]);
+}
+
+#[test]
+fn test_try_reclaim_drops_dead_values() {
+    let tracker = Arc::new(());
+    let mut vec = CowVec::new();
+    for _ in 0..10 {
+        vec.push(Arc::clone(&tracker));
+    }
+    vec.truncate(2);
+    assert_eq!(Arc::strong_count(&tracker), 11);
+    assert!(vec.try_reclaim());
+    assert_eq!(Arc::strong_count(&tracker), 3);
+}
+
+#[test]
+fn test_try_reclaim_shared_is_noop() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    vec.pop();
+    assert!(!vec.try_reclaim());
+    assert!(vec.shares_arena_with(&clone));
+    assert_eq!(vec.to_vec(), vec![1, 2]);
+    assert_eq!(clone.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_try_reclaim_with_handle_is_noop() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let handle = vec.handle(2).unwrap();
+    vec.pop();
+    assert!(!vec.try_reclaim());
+    assert_eq!(*handle.get(), 3);
+}
+
+#[test]
+fn test_try_reclaim_zero_sized() {
+    let mut vec = CowVec::from(vec![(), (), ()]);
+    vec.pop();
+    assert!(vec.try_reclaim());
+    assert_eq!(vec.len(), 2);
+    assert!(vec.is_compact());
+
+    vec.copy_within(0..1, 1);
+    assert!(vec.try_reclaim());
+    assert_eq!(vec.len(), 2);
+}
+
+#[test]
+fn test_try_reclaim_panicking_clone_leaves_vec_intact() {
+    #[derive(Debug, PartialEq)]
+    struct PanicOnClone(i32);
+    impl Clone for PanicOnClone {
+        fn clone(&self) -> Self {
+            panic!("clone failed");
+        }
+    }
+
+    let mut vec = CowVec::from(vec![PanicOnClone(1), PanicOnClone(2), PanicOnClone(3)]);
+    vec.pop();
+    // Both slots now point at the same value, so reclaiming must clone it.
+    vec.copy_within(0..1, 1);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.try_reclaim()));
+    assert!(result.is_err());
+    assert_eq!(vec, vec![PanicOnClone(1), PanicOnClone(1)]);
+}
+
+#[test]
+fn test_try_reclaim_panicking_drop_leaves_vec_intact() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct PanicOnDrop(i32);
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            if self.0 < 0 && !std::thread::panicking() {
+                panic!("drop failed");
+            }
+        }
+    }
+
+    let mut vec = CowVec::from(vec![PanicOnDrop(1), PanicOnDrop(2), PanicOnDrop(-1)]);
+    vec.pop();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.try_reclaim()));
+    assert!(result.is_err());
+    assert_eq!(vec, vec![PanicOnDrop(1), PanicOnDrop(2)]);
+    assert!(vec.is_compact());
+}
+
+// ============ try_push / try_reserve tests ============
+
+#[test]
+fn test_try_push_success() {
+    let vec1 = CowVec::from(vec![1, 2]);
+    let mut vec2 = vec1.clone();
+    assert!(vec2.try_push(3).is_ok());
+    assert_eq!(vec1.to_vec(), vec![1, 2]);
+    assert_eq!(vec2.to_vec(), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_try_push_reserves_arena_before_allocating() {
+    let mut vec = CowVec::from(vec![1u64, 2, 3, 4]);
+    assert_eq!(vec.arena_chunk_usage(), (4, 0));
+    assert!(vec.try_push(5).is_ok());
+    // The value lands in the chunk reserved for it; no second chunk is made.
+    assert_eq!(vec.arena_chunk_usage(), (8, 7));
+    assert_eq!(vec, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_try_reserve_impossible_capacity() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    assert!(vec.try_reserve(usize::MAX).is_err());
+    assert_eq!(vec.to_vec(), vec![1, 2, 3]);
+    assert!(vec.try_push(4).is_ok());
+}
+
+#[test]
+fn test_try_reserve_grows_items_and_arena() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    assert!(vec.try_reserve(100).is_ok());
+    assert!(vec.capacity() >= 103);
+    for i in 0..100 {
+        vec.push(i);
+    }
+    assert_eq!(vec.len(), 103);
+    assert_eq!(clone, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_try_reserve_predicts_arena_chunk_growth() {
+    // Starts with a single chunk of 4 values.
+    let mut vec = CowVec::from(vec![1u64, 2, 3, 4]);
+    assert_eq!(vec.arena_chunk_usage(), (4, 0));
+
+    // A single push doubles the chunk.
+    vec.push(5);
+    assert_eq!(vec.arena_chunk_usage(), (8, 7));
+
+    // A batch that does not fit gets a chunk of its own.
+    vec.extend(0..20);
+    assert_eq!(vec.arena_chunk_usage(), (32, 12));
+
+    // Another batch that does not fit doubles the chunk.
+    vec.extend(0..20);
+    assert_eq!(vec.arena_chunk_usage(), (64, 44));
+
+    // Reserving beyond the unused room allocates exactly the predicted chunk.
+    assert!(vec.try_reserve(100).is_ok());
+    assert_eq!(vec.arena_chunk_usage(), (128, 128));
+    assert!(vec.try_reserve(128).is_ok());
+    assert_eq!(vec.arena_chunk_usage(), (128, 128));
+}
+
+#[test]
+fn test_arena_chunk_tracking_survives_panicking_iterator() {
+    #[derive(Debug)]
+    struct PanicOnClone(u64);
+    impl Clone for PanicOnClone {
+        fn clone(&self) -> Self {
+            if self.0 == 5 {
+                panic!("clone failed");
+            }
+            PanicOnClone(self.0)
+        }
+    }
+
+    let mut vec = CowVec::from(vec![PanicOnClone(0), PanicOnClone(1)]);
+    let values: Vec<PanicOnClone> = (0..10).map(PanicOnClone).collect();
+    // The batch gets a new chunk of 16, then cloning stops after 5 values.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.insert_slice(0, &values);
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec.arena_chunk_usage(), (16, 11));
+    vec.push(PanicOnClone(2));
+    assert_eq!(vec.arena_chunk_usage(), (16, 10));
+}
+
+// ============ pointer storage invariant tests ============
+
+#[test]
+fn test_as_slice_layout_matches_get() {
+    let mut vec = CowVec::from(vec![1u8, 2, 3]);
+    vec.push(4);
+    vec.set(1, 20);
+    let slice = vec.as_slice();
+    for (i, item) in slice.iter().enumerate() {
+        assert!(std::ptr::eq(*item, vec.get(i).unwrap()));
+    }
+}
+
+// ============ as_ptr / as_ptr_slice tests ============
+
+#[test]
+fn test_as_ptr_points_at_first_element() {
+    let vec = CowVec::from(vec![10, 20, 30]);
+    let first = unsafe { *vec.as_ptr() };
+    assert!(std::ptr::eq(first, vec.get(0).unwrap()));
+    assert_eq!(unsafe { *first }, 10);
+}
+
+#[test]
+fn test_as_ptr_slice_matches_elements() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.set(1, 20);
+    let ptrs = vec2.as_ptr_slice();
+    assert_eq!(ptrs.len(), 3);
+    for (i, &ptr) in ptrs.iter().enumerate() {
+        assert!(std::ptr::eq(ptr, vec2.get(i).unwrap()));
+    }
+    assert_eq!(vec1.as_ptr_slice()[0], ptrs[0]);
+    assert_ne!(vec1.as_ptr_slice()[1], ptrs[1]);
+}
+
+// ============ concat tests ============
+
+#[test]
+fn test_concat_shared_arena() {
+    let mut parent = CowVec::from(vec![1, 2, 3, 4, 5, 6]);
+    let c = parent.split_off(4);
+    let b = parent.split_off(2);
+    let a = parent;
+    let nested = CowVec::from(vec![a.clone(), b, c]);
+    let flat = nested.concat();
+    assert_eq!(flat.to_vec(), vec![1, 2, 3, 4, 5, 6]);
+    assert!(flat.shares_arena_with(&a));
+    assert!(flat.ptr_eq_at(0, &a, 0));
+}
+
+#[test]
+fn test_concat_different_arenas() {
+    let a = CowVec::from(vec![1, 2]);
+    let b = CowVec::from(vec![3]);
+    let flat = CowVec::from(vec![a.clone(), b.clone()]).concat();
+    assert_eq!(flat.to_vec(), vec![1, 2, 3]);
+    assert!(!flat.shares_arena_with(&a));
+    assert!(!flat.shares_arena_with(&b));
+}
+
+#[test]
+fn test_concat_empty() {
+    let nested: CowVec<CowVec<i32>> = CowVec::new();
+    assert!(nested.concat().is_empty());
+}
+
+// ============ join tests ============
+
+#[test]
+fn test_join_with_separator() {
+    let nested = CowVec::from(vec![
+        CowVec::from(vec![1, 2]),
+        CowVec::from(vec![3]),
+        CowVec::from(vec![4, 5]),
+    ]);
+    assert_eq!(nested.join(&0).to_vec(), vec![1, 2, 0, 3, 0, 4, 5]);
+}
+
+#[test]
+fn test_join_shared_arena() {
+    let mut parent = CowVec::from(vec![1, 2, 3]);
+    let tail = parent.split_off(1);
+    let nested = CowVec::from(vec![parent.clone(), tail]);
+    let joined = nested.join(&0);
+    assert_eq!(joined.to_vec(), vec![1, 0, 2, 3]);
+    assert!(joined.shares_arena_with(&parent));
+}
+
+#[test]
+fn test_join_edge_cases() {
+    let empty: CowVec<CowVec<i32>> = CowVec::new();
+    assert!(empty.join(&0).is_empty());
+
+    let single = CowVec::from(vec![CowVec::from(vec![1, 2])]);
+    assert_eq!(single.join(&0).to_vec(), vec![1, 2]);
+
+    let with_empty = CowVec::from(vec![CowVec::new(), CowVec::from(vec![1])]);
+    assert_eq!(with_empty.join(&0).to_vec(), vec![0, 1]);
+}
+
+// ============ clone_from_slice / swap_with_slice tests ============
+
+#[test]
+fn test_clone_from_slice() {
+    let vec1 = CowVec::from(vec![1, 2, 3]);
+    let mut vec2 = vec1.clone();
+    vec2.clone_from_slice(&[4, 5, 6]);
+    assert_eq!(vec1.to_vec(), vec![1, 2, 3]);
+    assert_eq!(vec2.to_vec(), vec![4, 5, 6]);
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn test_clone_from_slice_length_mismatch() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.clone_from_slice(&[4, 5]);
+}
+
+#[test]
+fn test_swap_with_slice() {
+    let vec1 = CowVec::from(vec!["a".to_string(), "b".to_string()]);
+    let mut vec2 = vec1.clone();
+    let mut other = ["c".to_string(), "d".to_string()];
+    vec2.swap_with_slice(&mut other);
+    assert_eq!(vec1.to_vec(), vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(vec2.to_vec(), vec!["c".to_string(), "d".to_string()]);
+    assert_eq!(other, ["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn test_swap_with_slice_length_mismatch() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.swap_with_slice(&mut [4, 5, 6, 7]);
+}
+
+#[test]
+fn test_swap_with_slice_panicking_clone_leaves_both_intact() {
+    #[derive(Debug, PartialEq)]
+    struct PanicOnClone(i32);
+    impl Clone for PanicOnClone {
+        fn clone(&self) -> Self {
+            if self.0 == 2 {
+                panic!("clone failed");
+            }
+            PanicOnClone(self.0)
+        }
+    }
+
+    let mut vec = CowVec::from(vec![PanicOnClone(1), PanicOnClone(2), PanicOnClone(3)]);
+    let mut other = [PanicOnClone(4), PanicOnClone(5), PanicOnClone(6)];
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.swap_with_slice(&mut other);
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec, vec![PanicOnClone(1), PanicOnClone(2), PanicOnClone(3)]);
+    assert_eq!(other, [PanicOnClone(4), PanicOnClone(5), PanicOnClone(6)]);
+}
+
+// ============ PartialEq between CowVecs ============
+
+#[test]
+fn test_eq_cow_vecs_matches_reference() {
+    let base = CowVec::from((0..1000).collect::<Vec<i32>>());
+    let mut edited = base.clone();
+    assert_eq!(base, edited);
+
+    for i in (0..1000).step_by(100) {
+        edited.set(i, i as i32);
+    }
+    // Same values in new slots: still equal.
+    assert_eq!(base, edited);
+    assert_eq!(base == edited, base.to_vec() == edited.to_vec());
+
+    edited.set(500, -1);
+    assert_ne!(base, edited);
+    assert_eq!(base == edited, base.to_vec() == edited.to_vec());
+}
+
+#[test]
+fn test_eq_independent_cow_vecs() {
+    let a = CowVec::from(vec![1, 2, 3]);
+    let b = CowVec::from(vec![1, 2, 3]);
+    let c = CowVec::from(vec![1, 2]);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_eq_skips_identical_pointers() {
+    use std::cell::Cell;
+
+    struct Counted<'a>(i32, &'a Cell<usize>);
+    impl PartialEq for Counted<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.1.set(self.1.get() + 1);
+            self.0 == other.0
+        }
+    }
+
+    let comparisons = Cell::new(0);
+    let base = CowVec::from(
+        (0..100)
+            .map(|i| Counted(i, &comparisons))
+            .collect::<Vec<_>>(),
+    );
+    let mut edited = base.clone();
+    edited.set(10, Counted(10, &comparisons));
+    edited.set(20, Counted(20, &comparisons));
+
+    assert!(base == edited);
+    assert_eq!(comparisons.get(), 2);
+}
+
+// ============ Hash / HashCached tests ============
+
+fn hash_of<H: std::hash::Hash>(value: &H) -> u64 {
+    use std::hash::{DefaultHasher, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_hash_matches_for_equal_contents() {
+    let a = CowVec::from(vec![1, 2, 3]);
+    let mut b = CowVec::from(vec![0, 2, 3]);
+    b.set(0, 1);
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_ne!(hash_of(&a), hash_of(&CowVec::from(vec![1, 2])));
+}
+
+#[test]
+fn test_hash_cached_changes_after_mutation() {
+    use super::HashCached;
+
+    let mut key = HashCached::new(CowVec::from(vec![1, 2, 3]));
+    let before = hash_of(&key);
+    key.get_mut().push(4);
+    assert_ne!(hash_of(&key), before);
+    assert_eq!(
+        hash_of(&key),
+        hash_of(&HashCached::new(CowVec::from(vec![1, 2, 3, 4])))
+    );
+}
+
+#[test]
+fn test_hash_cached_does_not_retraverse() {
+    use super::HashCached;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static HASHED: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(PartialEq, Eq)]
+    struct Counted(i32);
+    impl std::hash::Hash for Counted {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            HASHED.fetch_add(1, Ordering::Relaxed);
+            self.0.hash(state);
+        }
+    }
+
+    let key = HashCached::new(CowVec::from(vec![Counted(1), Counted(2), Counted(3)]));
+    let first = hash_of(&key);
+    assert_eq!(HASHED.load(Ordering::Relaxed), 3);
+    assert_eq!(hash_of(&key), first);
+    assert_eq!(hash_of(&key), first);
+    assert_eq!(HASHED.load(Ordering::Relaxed), 3);
+
+    let mut clone = key.clone();
+    clone.get_mut().push(Counted(4));
+    assert_ne!(hash_of(&clone), first);
+    assert_eq!(HASHED.load(Ordering::Relaxed), 7);
+    assert_eq!(hash_of(&key), first);
+    assert_eq!(HASHED.load(Ordering::Relaxed), 7);
+}
+
+#[test]
+// The arena's `Mutex` never affects hashing or equality.
+#[allow(clippy::mutable_key_type)]
+fn test_hash_cached_as_map_key() {
+    use super::HashCached;
+    use std::collections::HashMap;
+
+    let mut map = HashMap::new();
+    map.insert(HashCached::new(CowVec::from(vec![1, 2])), "a");
+    map.insert(HashCached::new(CowVec::from(vec![3])), "b");
+    assert_eq!(
+        map.get(&HashCached::new(CowVec::from(vec![1, 2]))),
+        Some(&"a")
+    );
+    assert_eq!(map.get(&HashCached::new(CowVec::from(vec![3]))), Some(&"b"));
+}
+
+// ============ write_to / read_from tests ============
+
+#[test]
+fn test_write_read_round_trip() {
+    let vec = CowVec::from(vec![1u32, 0xDEAD_BEEF, 42, u32::MAX]);
+    let mut buf = Vec::new();
+    unsafe { vec.write_to(&mut buf) }.unwrap();
+    assert_eq!(buf.len(), 8 + 4 * 4);
+    assert_eq!(&buf[..8], &4u64.to_le_bytes());
+
+    let back = unsafe { CowVec::<u32>::read_from(&mut buf.as_slice(), 4) }.unwrap();
+    assert_eq!(back, vec);
+}
+
+#[test]
+fn test_write_read_empty() {
+    let vec: CowVec<f64> = CowVec::new();
+    let mut buf = Vec::new();
+    unsafe { vec.write_to(&mut buf) }.unwrap();
+    let back = unsafe { CowVec::<f64>::read_from(&mut buf.as_slice(), 0) }.unwrap();
+    assert!(back.is_empty());
+}
+
+#[test]
+fn test_read_from_length_mismatch() {
+    let vec = CowVec::from(vec![1u16, 2, 3]);
+    let mut buf = Vec::new();
+    unsafe { vec.write_to(&mut buf) }.unwrap();
+    let err = unsafe { CowVec::<u16>::read_from(&mut buf.as_slice(), 2) }.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_read_from_truncated_input() {
+    let vec = CowVec::from(vec![1u64, 2]);
+    let mut buf = Vec::new();
+    unsafe { vec.write_to(&mut buf) }.unwrap();
+    buf.pop();
+    let err = unsafe { CowVec::<u64>::read_from(&mut buf.as_slice(), 2) }.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+// ============ leak tests ============
+
+#[test]
+fn test_leak_outlives_binding() {
+    let leaked: &'static [&'static String] = {
+        let vec = CowVec::from(vec!["x".to_string(), "y".to_string()]);
+        vec.leak()
+    };
+    assert_eq!(leaked.len(), 2);
+    assert_eq!(leaked[0], "x");
+    assert_eq!(leaked[1], "y");
+}
+
+#[test]
+fn test_leak_shared_structure() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    let leaked = vec.leak();
+    assert_eq!(
+        leaked.iter().map(|x| **x).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(clone, vec![1, 2, 3]);
+}
+
+// ============ rchunks / rchunks_exact tests ============
+
+#[test]
+fn test_rchunks() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let chunks: Vec<Vec<i32>> = vec
+        .rchunks(2)
+        .map(|chunk| chunk.iter().map(|x| **x).collect())
+        .collect();
+    assert_eq!(chunks, vec![vec![4, 5], vec![2, 3], vec![1]]);
+}
+
+#[test]
+fn test_rchunks_exact() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let mut chunks = vec.rchunks_exact(2);
+    assert_eq!(chunks.next(), Some(&[&4, &5][..]));
+    assert_eq!(chunks.next(), Some(&[&2, &3][..]));
+    assert_eq!(chunks.next(), None);
+    assert_eq!(chunks.remainder(), &[&1]);
+}
+
+#[test]
+#[should_panic]
+fn test_rchunks_zero() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let _ = vec.rchunks(0);
+}
+
+// ============ split_at_mut tests ============
+
+#[test]
+fn test_split_at_mut_isolated_halves() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let snapshot = vec.clone();
+    let (mut head, mut tail) = vec.split_at_mut(2);
+    assert!(vec.is_empty());
+    assert!(head.shares_arena_with(&tail));
+
+    head.set(0, 10);
+    tail.push(6);
+    tail.set(0, 30);
+    assert_eq!(head, vec![10, 2]);
+    assert_eq!(tail, vec![30, 4, 5, 6]);
+    assert_eq!(snapshot, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_split_at_mut_edges() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let (head, tail) = vec.split_at_mut(0);
+    assert!(head.is_empty());
+    assert_eq!(tail, vec![1, 2, 3]);
+
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let (head, tail) = vec.split_at_mut(3);
+    assert_eq!(head, vec![1, 2, 3]);
+    assert!(tail.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "mid > len")]
+fn test_split_at_mut_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.split_at_mut(4);
+}
+
+// ============ set_in_place tests ============
+
+#[test]
+fn test_set_in_place_unique_reuses_slot() {
+    let mut vec = CowVec::from(vec![String::from("a"), String::from("b")]);
+    let before = vec.memory_usage();
+    let slot = vec.as_ptr_slice()[1];
+    for i in 0..10 {
+        vec.set_in_place(1, i.to_string());
+    }
+    assert_eq!(vec, vec![String::from("a"), String::from("9")]);
+    assert_eq!(vec.as_ptr_slice()[1], slot);
+    assert_eq!(vec.memory_usage(), before);
+}
+
+#[test]
+fn test_set_in_place_falls_back_when_shared() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    let slot = vec.as_ptr_slice()[0];
+    vec.set_in_place(0, 10);
+    assert_eq!(vec, vec![10, 2, 3]);
+    assert_eq!(clone, vec![1, 2, 3]);
+    assert_ne!(vec.as_ptr_slice()[0], slot);
+
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let tail = vec.split_off(2);
+    vec.set_in_place(0, 10);
+    assert_eq!(vec, vec![10, 2]);
+    assert_eq!(tail, vec![3]);
+
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let handle = vec.handle(0).unwrap();
+    vec.set_in_place(0, 10);
+    assert_eq!(*handle.get(), 1);
+    assert_eq!(vec[0], 10);
+}
+
+#[test]
+fn test_set_in_place_falls_back_for_duplicate_slots() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.copy_within(0..1, 2);
+    vec.set_in_place(0, 10);
+    assert_eq!(vec, vec![10, 2, 1]);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_set_in_place_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.set_in_place(3, 0);
+}
+
+// ============ reserve_exact / shrink_to tests ============
+
+#[test]
+fn test_reserve_exact() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.reserve_exact(10);
+    assert!(vec.capacity() >= 13);
+    assert_eq!(vec, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_shrink_to_preserves_contents() {
+    let mut vec = CowVec::from((0..100).collect::<Vec<i32>>());
+    vec.truncate(5);
+    assert!(vec.capacity() >= 100);
+    vec.shrink_to(20);
+    assert!(vec.capacity() >= 20);
+    assert!(vec.capacity() < 100);
+    assert_eq!(vec, vec![0, 1, 2, 3, 4]);
+
+    vec.shrink_to(0);
+    assert!(vec.capacity() >= 5);
+    assert_eq!(vec, vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn test_shrink_to_does_not_affect_clone() {
+    let mut vec = CowVec::from((0..50).collect::<Vec<i32>>());
+    let clone = vec.clone();
+    vec.truncate(2);
+    vec.shrink_to(2);
+    assert_eq!(vec, vec![0, 1]);
+    assert_eq!(clone.len(), 50);
+}
+
+// ============ take_while / skip_while tests ============
+
+#[test]
+fn test_take_while_shares_arena() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let head = vec.take_while(|&x| x < 3);
+    assert_eq!(head, vec![1, 2]);
+    assert!(head.shares_arena_with(&vec));
+    assert!(head.ptr_eq_at(0, &vec, 0));
+}
+
+#[test]
+fn test_skip_while() {
+    let vec = CowVec::from(vec![1, 2, 3, 1]);
+    let tail = vec.skip_while(|&x| x < 3);
+    assert_eq!(tail, vec![3, 1]);
+    assert!(tail.shares_arena_with(&vec));
+}
+
+#[test]
+fn test_take_skip_while_edges() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec.take_while(|_| true), vec![1, 2, 3]);
+    assert!(vec.take_while(|_| false).is_empty());
+    assert!(vec.skip_while(|_| true).is_empty());
+    assert_eq!(vec.skip_while(|_| false), vec![1, 2, 3]);
+}
+
+// ============ count / count_eq tests ============
+
+#[test]
+fn test_count_predicate() {
+    let vec = CowVec::from((1..7).collect::<Vec<i32>>());
+    assert_eq!(vec.count(|&x| x % 2 == 0), 3);
+    assert_eq!(vec.count(|_| false), 0);
+    assert_eq!(CowVec::<i32>::new().count(|_| true), 0);
+}
+
+#[test]
+fn test_count_eq() {
+    let vec = CowVec::from(vec![1, 2, 1, 3, 1]);
+    assert_eq!(vec.count_eq(&1), 3);
+    assert_eq!(vec.count_eq(&2), 1);
+    assert_eq!(vec.count_eq(&4), 0);
+}
+
+// ============ with_capacities tests ============
+
+#[test]
+fn test_with_capacities() {
+    let mut vec: CowVec<i32> = CowVec::with_capacities(4, 100);
+    assert!(vec.is_empty());
+    assert!(vec.capacity() >= 4);
+    assert_eq!(
+        vec.memory_usage(),
+        vec.capacity() * std::mem::size_of::<usize>()
+    );
+
+    vec.push(1);
+    for i in 0..50 {
+        vec.set(0, i);
+    }
+    assert_eq!(vec, vec![49]);
+}
+
+// ============ truncate_and_reclaim tests ============
+
+#[test]
+fn test_truncate_and_reclaim_sole_owner() {
+    let mut vec = CowVec::from((0..100).map(|i| i.to_string()).collect::<Vec<_>>());
+    let before = vec.memory_usage();
+    vec.truncate_and_reclaim(3);
+    assert_eq!(vec, vec!["0".to_string(), "1".to_string(), "2".to_string()]);
+    assert!(vec.is_compact());
+    assert!(vec.memory_usage() < before);
+}
+
+#[test]
+fn test_truncate_and_reclaim_shared() {
+    let mut vec = CowVec::from((0..100).collect::<Vec<i32>>());
+    let clone = vec.clone();
+    vec.truncate_and_reclaim(3);
+    assert_eq!(vec, vec![0, 1, 2]);
+    assert!(!vec.is_compact());
+    assert!(vec.shares_arena_with(&clone));
+    assert_eq!(clone.len(), 100);
+}
+
+#[test]
+fn test_truncate_and_reclaim_longer_len_is_noop() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.set(0, 10);
+    vec.truncate_and_reclaim(5);
+    assert_eq!(vec, vec![10, 2, 3]);
+    assert!(!vec.is_compact());
+}
+
+#[test]
+fn test_truncate_and_reclaim_zero_sized() {
+    let mut vec = CowVec::from(vec![(); 10]);
+    vec.truncate_and_reclaim(4);
+    assert_eq!(vec.len(), 4);
+    assert!(vec.is_compact());
+}
+
+// ============ find_map / filter_map tests ============
+
+#[test]
+fn test_find_map() {
+    let vec = CowVec::from(vec!["a", "b", "42", "7"]);
+    assert_eq!(vec.find_map(|s| s.parse::<i32>().ok()), Some(42));
+    assert_eq!(vec.find_map(|s| s.strip_prefix('z')), None);
+}
+
+#[test]
+fn test_filter_map() {
+    let vec = CowVec::from(vec!["1", "two", "3", "", "5"]);
+    assert_eq!(vec.filter_map(|s| s.parse::<i32>().ok()), vec![1, 3, 5]);
+    assert!(CowVec::<&str>::new()
+        .filter_map(|s| s.parse::<i32>().ok())
+        .is_empty());
+}
+
+// ============ contains_by_borrow tests ============
+
+#[test]
+fn test_contains_by_borrow() {
+    let vec = CowVec::from(vec!["foo".to_string(), "bar".to_string()]);
+    assert!(vec.contains_by_borrow("foo"));
+    assert!(vec.contains_by_borrow("bar"));
+    assert!(!vec.contains_by_borrow("baz"));
+    assert!(vec.contains_by_borrow(&"foo".to_string()));
+
+    let boxed = CowVec::from(vec![vec![1, 2], vec![3]]);
+    assert!(boxed.contains_by_borrow(&[3][..]));
+}
+
+// ============ map tests ============
+
+#[test]
+fn test_map_to_new_type() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let strings: CowVec<String> = vec.map(|x| format!("#{x}"));
+    assert_eq!(
+        strings,
+        vec!["#1".to_string(), "#2".to_string(), "#3".to_string()]
+    );
+    assert!(strings.is_compact());
+    assert_eq!(vec, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_map_empty_and_same_type() {
+    let empty: CowVec<i32> = CowVec::new();
+    assert!(empty.map(|x| x * 2).is_empty());
+
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let doubled = vec.map(|x| x * 2);
+    assert_eq!(doubled, vec![2, 4, 6]);
+    assert!(!doubled.shares_arena_with(&vec));
+}
+
+// ============ map_in_place tests ============
+
+#[test]
+fn test_map_in_place_preserves_clone() {
+    fn arena_bytes(vec: &CowVec<i32>) -> usize {
+        vec.memory_usage() - vec.capacity() * std::mem::size_of::<usize>()
+    }
+
+    let source = CowVec::from(vec![1, 2, 3, 4]);
+    let mut doubled = source.clone();
+    let before = arena_bytes(&source);
+    doubled.map_in_place(|x| x * 2);
+
+    assert_eq!(doubled, vec![2, 4, 6, 8]);
+    assert_eq!(source, vec![1, 2, 3, 4]);
+    assert!(doubled.shares_arena_with(&source));
+    assert_eq!(
+        arena_bytes(&doubled) - before,
+        4 * std::mem::size_of::<i32>()
+    );
+}
+
+#[test]
+fn test_map_in_place_empty() {
+    let mut vec: CowVec<i32> = CowVec::new();
+    vec.map_in_place(|x| x + 1);
+    assert!(vec.is_empty());
+}
+
+// ============ fold / reduce tests ============
+
+#[test]
+fn test_fold_sum() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    assert_eq!(vec.fold(0, |sum, x| sum + x), 15);
+    assert_eq!(
+        vec.fold(String::new(), |acc, x| acc + &x.to_string()),
+        "12345"
+    );
+}
+
+#[test]
+fn test_reduce_max() {
+    let vec = CowVec::from(vec![3, 9, 1, 4]);
+    assert_eq!(vec.reduce(|a, b| *a.max(b)), Some(9));
+    assert_eq!(CowVec::from(vec![5]).reduce(|a, b| a + b), Some(5));
+    assert_eq!(CowVec::<i32>::new().reduce(|a, b| a + b), None);
+}
+
+// ============ iter_mut_enumerated tests ============
+
+#[test]
+fn test_iter_mut_enumerated_position_dependent_edit() {
+    let mut vec = CowVec::from(vec![10, 20, 30]);
+    let original = vec.clone();
+    for (i, x) in vec.iter_mut_enumerated() {
+        *x *= i as i32;
+    }
+    assert_eq!(vec, vec![0, 20, 60]);
+    assert_eq!(original, vec![10, 20, 30]);
+}
+
+#[test]
+fn test_iter_mut_enumerated_only_visited_diverge() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4]);
+    let original = vec.clone();
+    for (i, x) in vec.iter_mut_enumerated() {
+        if i == 1 {
+            break;
+        }
+        *x = 100;
+    }
+    // Visited elements get new slots even if untouched; later ones stay shared.
+    assert_eq!(vec, vec![100, 2, 3, 4]);
+    assert_eq!(vec.diff(&original), vec![0, 1]);
+    assert_eq!(original, vec![1, 2, 3, 4]);
+}
+
+// ============ concat_with tests ============
+
+#[test]
+fn test_concat_with_shares_arena() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let combined = vec.concat_with(&[4, 5]);
+    assert_eq!(combined, vec![1, 2, 3, 4, 5]);
+    assert_eq!(vec, vec![1, 2, 3]);
+    assert!(combined.shares_arena_with(&vec));
+    assert!(combined.ptr_eq_at(0, &vec, 0));
+    assert!(combined.ptr_eq_at(2, &vec, 2));
+}
+
+#[test]
+fn test_concat_with_empty() {
+    let vec = CowVec::from(vec![1, 2]);
+    assert_eq!(vec.concat_with(&[]), vec![1, 2]);
+    assert_eq!(CowVec::new().concat_with(&[7]), vec![7]);
+}
+
+// ============ append_vec tests ============
+
+#[test]
+fn test_append_vec_preserves_order() {
+    let mut vec = CowVec::from(vec!["a".to_string()]);
+    let clone = vec.clone();
+    let other = vec!["b".to_string(), "c".to_string()];
+    vec.append_vec(other);
+    assert_eq!(vec, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    assert_eq!(clone, vec!["a".to_string()]);
+}
+
+#[test]
+fn test_append_vec_moves_without_cloning() {
+    struct NoClone(i32);
+
+    let mut vec = CowVec::new();
+    vec.append_vec(vec![NoClone(1), NoClone(2)]);
+    vec.append_vec(Vec::new());
+    assert_eq!(vec.iter().map(|x| x.0).collect::<Vec<_>>(), vec![1, 2]);
+}
+
+// ============ clear_keep_capacity / reset tests ============
+
+#[test]
+fn test_clear_keep_capacity() {
+    let mut vec = CowVec::from((0..50).collect::<Vec<i32>>());
+    let capacity = vec.capacity();
+    vec.clear_keep_capacity();
+    assert!(vec.is_empty());
+    assert_eq!(vec.capacity(), capacity);
+
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    vec.clear_keep_capacity();
+    assert!(vec.is_empty());
+    assert_eq!(clone, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_reset_sole_owner_drops_garbage() {
+    use std::rc::Rc;
+
+    let marker = Rc::new(());
+    let mut vec = CowVec::from(vec![Rc::clone(&marker), Rc::clone(&marker)]);
+    vec.set(0, Rc::clone(&marker));
+    vec.pop();
+    assert_eq!(Rc::strong_count(&marker), 4);
+
+    vec.reset();
+    assert!(vec.is_empty());
+    assert_eq!(Rc::strong_count(&marker), 1);
+
+    vec.push(Rc::clone(&marker));
+    assert_eq!(vec.len(), 1);
+}
+
+#[test]
+fn test_reset_shared_arena_keeps_values_alive() {
+    use std::rc::Rc;
+
+    let marker = Rc::new(());
+    let mut vec = CowVec::from(vec![Rc::clone(&marker)]);
+    let clone = vec.clone();
+    vec.reset();
+    assert!(!vec.shares_arena_with(&clone));
+    assert_eq!(Rc::strong_count(&marker), 2);
+    drop(clone);
+    assert_eq!(Rc::strong_count(&marker), 1);
+}
+
+// ============ split / rsplit tests ============
+
+#[test]
+fn test_split_on_delimiter() {
+    let vec = CowVec::from(vec![1, 0, 2, 0, 3]);
+    assert_eq!(
+        segments(vec.split(|&x| x == 0)),
+        vec![vec![1], vec![2], vec![3]]
+    );
+}
+
+#[test]
+fn test_split_empty_segments() {
+    let vec = CowVec::from(vec![0, 1, 0, 0, 2, 0]);
+    assert_eq!(
+        segments(vec.split(|&x| x == 0)),
+        vec![vec![], vec![1], vec![], vec![2], vec![]]
+    );
+    let empty: CowVec<i32> = CowVec::new();
+    assert_eq!(segments(empty.split(|&x| x == 0)), vec![Vec::<i32>::new()]);
+}
+
+#[test]
+fn test_rsplit_matches_reversed_split() {
+    let vec = CowVec::from(vec![0, 1, 2, 0, 3, 0]);
+    let mut forward = segments(vec.split(|&x| x == 0));
+    forward.reverse();
+    assert_eq!(segments(vec.rsplit(|&x| x == 0)), forward);
+    assert_eq!(
+        segments(vec.rsplit(|&x| x == 0)),
+        vec![vec![], vec![3], vec![1, 2], vec![]]
+    );
+}
+
+// ============ try_into_array tests ============
+
+#[test]
+fn test_try_into_array_exact() {
+    let vec = CowVec::from(vec!["r".to_string(), "g".to_string(), "b".to_string()]);
+    let array: [String; 3] = vec.try_into_array().unwrap();
+    assert_eq!(array, ["r".to_string(), "g".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn test_try_into_array_wrong_length() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+
+    let too_short = vec.try_into_array::<4>().unwrap_err();
+    assert_eq!(too_short, vec![1, 2, 3]);
+    assert!(too_short.shares_arena_with(&vec));
+
+    let too_long = vec.try_into_array::<2>().unwrap_err();
+    assert_eq!(too_long, vec![1, 2, 3]);
+
+    let empty: CowVec<i32> = CowVec::new();
+    assert_eq!(empty.try_into_array::<0>().unwrap(), []);
+}
+
+// ============ for_each_window tests ============
+
+#[test]
+fn test_for_each_window_counts() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    for (size, expected) in [(1, 5), (2, 4), (3, 3), (5, 1), (6, 0)] {
+        let mut visited = 0;
+        vec.for_each_window(size, |window| {
+            assert_eq!(window.len(), size);
+            visited += 1;
+        });
+        assert_eq!(visited, expected, "size {size}");
+    }
+}
+
+#[test]
+fn test_for_each_window_contents() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    let mut windows = Vec::new();
+    vec.for_each_window(2, |w| windows.push((*w[0], *w[1])));
+    assert_eq!(windows, vec![(1, 2), (2, 3)]);
+}
+
+#[test]
+#[should_panic(expected = "window size must be non-zero")]
+fn test_for_each_window_zero() {
+    CowVec::from(vec![1]).for_each_window(0, |_| {});
+}
+
+// ============ swap_with tests ============
+
+#[test]
+fn test_swap_with_shared_arena() {
+    let base = CowVec::from(vec![1, 2, 3]);
+    let mut a = base.clone();
+    let mut b = base.subvec(1..);
+    a.swap_with(0, &mut b, 1);
+    assert_eq!(a, vec![3, 2, 3]);
+    assert_eq!(b, vec![2, 1]);
+    assert_eq!(base, vec![1, 2, 3]);
+    assert!(a.ptr_eq_at(0, &base, 2));
+}
+
+#[test]
+#[should_panic(expected = "share an arena")]
+fn test_swap_with_unrelated_arenas() {
+    let mut a = CowVec::from(vec![1]);
+    let mut b = CowVec::from(vec![2]);
+    a.swap_with(0, &mut b, 0);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_swap_with_out_of_bounds() {
+    let mut a = CowVec::from(vec![1, 2]);
+    let mut b = a.clone();
+    a.swap_with(0, &mut b, 2);
+}
+
+// ============ sort_stable tests ============
+
+#[test]
+fn test_sort_stable_keeps_equal_order() {
+    #[derive(Debug, Clone)]
+    struct Keyed(u32, &'static str);
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Keyed {}
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let original = CowVec::from(vec![
+        Keyed(2, "a"),
+        Keyed(1, "b"),
+        Keyed(2, "c"),
+        Keyed(1, "d"),
+        Keyed(0, "e"),
+    ]);
+    let mut sorted = original.clone();
+    sorted.sort_stable();
+    let labels: Vec<&str> = sorted.iter().map(|k| k.1).collect();
+    assert_eq!(labels, vec!["e", "b", "d", "a", "c"]);
+    assert!(sorted.ptr_eq_at(1, &original, 1));
+    assert!(sorted.ptr_eq_at(3, &original, 0));
+    assert_eq!(original.iter().map(|k| k.1).collect::<String>(), "abcde");
+}
+
+// ============ dedup_returning tests ============
+
+#[test]
+fn test_dedup_returning() {
+    let mut vec = CowVec::from(vec![1, 1, 2, 2, 2, 3]);
+    let clone = vec.clone();
+    let removed: Vec<i32> = vec.dedup_returning().into_iter().copied().collect();
+    assert_eq!(removed, vec![1, 2, 2]);
+    assert_eq!(vec, vec![1, 2, 3]);
+    assert_eq!(clone, vec![1, 1, 2, 2, 2, 3]);
+}
+
+#[test]
+fn test_dedup_returning_nothing_to_remove() {
+    let mut vec = CowVec::from(vec![1, 2, 1]);
+    assert!(vec.dedup_returning().is_empty());
+    assert_eq!(vec, vec![1, 2, 1]);
+
+    let mut empty: CowVec<i32> = CowVec::new();
+    assert!(empty.dedup_returning().is_empty());
+}
+
+// ============ iter_ptr tests ============
+
+#[test]
+fn test_iter_ptr_single_set_differs_once() {
+    let base = CowVec::from((0..10).collect::<Vec<i32>>());
+    let mut edited = base.clone();
+    edited.set(4, 40);
+    let differing: Vec<usize> = base
+        .iter_ptr()
+        .zip(edited.iter_ptr())
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(differing, vec![4]);
+}
+
+#[test]
+fn test_iter_ptr_matches_elements() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    for (ptr, item) in vec.iter_ptr().zip(vec.iter()) {
+        assert!(std::ptr::eq(ptr, item));
+    }
+    assert_eq!(vec.iter_ptr().count(), 3);
+}
+
+// ============ compact_to_capacity tests ============
+
+#[test]
+fn test_compact_to_capacity_sole_owner() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    for i in 0..50 {
+        vec.set(1, i);
+    }
+    assert!(!vec.compact_to_capacity(100));
+    assert!(!vec.is_compact());
+
+    assert!(vec.compact_to_capacity(10));
+    assert!(vec.is_compact());
+    assert_eq!(vec, vec![1, 49, 3]);
+}
+
+#[test]
+fn test_compact_to_capacity_shared_is_noop() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    for i in 0..50 {
+        vec.set(1, i);
+    }
+    let clone = vec.clone();
+    assert!(!vec.compact_to_capacity(10));
+    assert!(vec.shares_arena_with(&clone));
+    assert!(!vec.is_compact());
+}
+
+#[test]
+fn test_compact_to_capacity_zero_sized() {
+    let mut vec = CowVec::from(vec![(), ()]);
+    for _ in 0..20 {
+        vec.set(0, ());
+    }
+    assert!(vec.compact_to_capacity(10));
+    assert_eq!(vec.len(), 2);
+    assert!(vec.is_compact());
+}
+
+// ============ from_vec_with_capacity tests ============
+
+#[test]
+fn test_from_vec_with_capacity() {
+    let mut vec = CowVec::from_vec_with_capacity(vec![1, 2, 3], 64);
+    assert_eq!(vec, vec![1, 2, 3]);
+    assert!(vec.is_compact());
+
+    for i in 0..64 {
+        vec.set(i % 3, i as i32);
+    }
+    vec.push(100);
+    assert_eq!(vec, vec![63, 61, 62, 100]);
+
+    let empty: CowVec<i32> = CowVec::from_vec_with_capacity(Vec::new(), 8);
+    assert!(empty.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn test_from_vec_with_capacity_overflowing_sum() {
+    let _ = CowVec::from_vec_with_capacity(vec![1, 2, 3], usize::MAX);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn test_from_vec_with_capacity_too_large_for_arena() {
+    let _ = CowVec::<u64>::from_vec_with_capacity(Vec::new(), usize::MAX);
+}
+
+// ============ rotate tests ============
+
+#[test]
+fn test_rotate_directions() {
+    let base = CowVec::from(vec![1, 2, 3, 4, 5]);
+
+    let mut vec = base.clone();
+    vec.rotate(1);
+    assert_eq!(vec, vec![2, 3, 4, 5, 1]);
+
+    let mut vec = base.clone();
+    vec.rotate(-1);
+    assert_eq!(vec, vec![5, 1, 2, 3, 4]);
+
+    let mut vec = base.clone();
+    vec.rotate(0);
+    assert_eq!(vec, vec![1, 2, 3, 4, 5]);
+    assert!(vec.is_structure_shared());
+
+    assert_eq!(base, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_rotate_wraps_modulo_len() {
+    let base = CowVec::from(vec![1, 2, 3, 4, 5]);
+
+    let mut vec = base.clone();
+    vec.rotate(7);
+    assert_eq!(vec, vec![3, 4, 5, 1, 2]);
+
+    let mut vec = base.clone();
+    vec.rotate(-12);
+    assert_eq!(vec, vec![4, 5, 1, 2, 3]);
+
+    let mut vec = base.clone();
+    vec.rotate(isize::MIN);
+    let mut expected = base.to_vec();
+    expected.rotate_left(isize::MIN.rem_euclid(5) as usize);
+    assert_eq!(vec, expected);
+
+    let mut empty: CowVec<i32> = CowVec::new();
+    empty.rotate(3);
+    assert!(empty.is_empty());
+}
+
+// ============ values_eq tests ============
+
+#[test]
+fn test_values_eq_agrees_with_partial_eq() {
+    let base = CowVec::from((0..100).collect::<Vec<i32>>());
+    let mut shared = base.clone();
+    shared.set(10, 10);
+    let unshared = CowVec::from((0..100).collect::<Vec<i32>>());
+    let mut different = base.clone();
+    different.set(50, -1);
+
+    for other in [&base, &shared, &unshared, &different] {
+        assert_eq!(base.values_eq(other), base == *other);
+    }
+    assert!(!base.values_eq(&base.subvec(..50)));
+}
+
+#[test]
+fn test_values_eq_bypasses_pointer_fast_path() {
+    let vec = CowVec::from(vec![f64::NAN]);
+    let clone = vec.clone();
+    assert!(vec == clone);
+    assert!(!vec.values_eq(&clone));
+}
+
+// ============ push_from tests ============
+
+#[test]
+fn test_push_from_aliases_slot() {
+    let source = CowVec::from(vec![10, 20, 30]);
+    let mut picked = CowVec::in_arena_of(&source);
+    picked.push_from(&source, 1);
+    picked.push_from(&source, 1);
+    assert_eq!(picked, vec![20, 20]);
+    assert!(picked.ptr_eq_at(0, &source, 1));
+    assert!(picked.ptr_eq_at(1, &source, 1));
+}
+
+#[test]
+fn test_push_from_clone_isolation() {
+    let mut source = CowVec::from(vec![10, 20, 30]);
+    let mut picked = CowVec::in_arena_of(&source);
+    picked.push_from(&source, 0);
+
+    source.set(0, 11);
+    assert_eq!(picked, vec![10]);
+    picked.set(0, 12);
+    assert_eq!(source, vec![11, 20, 30]);
+    assert_eq!(picked, vec![12]);
+}
+
+#[test]
+#[should_panic(expected = "share an arena")]
+fn test_push_from_unrelated_arenas() {
+    let source = CowVec::from(vec![1]);
+    let mut other = CowVec::from(vec![2]);
+    other.push_from(&source, 0);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds")]
+fn test_push_from_out_of_bounds() {
+    let source = CowVec::from(vec![1]);
+    let mut other = source.clone();
+    other.push_from(&source, 1);
+}
+
+// ============ CowVecBuilder tests ============
+
+#[test]
+fn test_builder_capacities() {
+    use super::CowVecBuilder;
+
+    let vec = CowVecBuilder::<i32>::new()
+        .items_capacity(32)
+        .arena_capacity(128)
+        .build();
+    assert!(vec.is_empty());
+    assert!(vec.capacity() >= 32);
+    assert_eq!(vec.threshold(), None);
+}
+
+#[test]
+fn test_builder_auto_compacts_clones() {
+    use super::CowVecBuilder;
+
+    let mut vec = CowVecBuilder::new().auto_compact_threshold(5).build();
+    vec.extend([1, 2, 3]);
+    let small = vec.clone();
+    assert!(small.shares_arena_with(&vec));
+
+    for i in 0..10 {
+        vec.set(0, i);
+    }
+    let compacted = vec.clone();
+    assert!(!compacted.shares_arena_with(&vec));
+    assert!(compacted.is_compact());
+    assert_eq!(compacted.to_vec(), vec![9, 2, 3]);
+    assert_eq!(compacted.threshold(), Some(5));
+}
+
+#[test]
+fn test_builder_without_threshold_shares_arena() {
+    use super::CowVecBuilder;
+
+    let mut vec = CowVecBuilder::new().build();
+    vec.push(1);
+    for i in 0..10 {
+        vec.set(0, i);
+    }
+    let clone = vec.clone();
+    assert!(clone.shares_arena_with(&vec));
+    assert_eq!(clone.into_inner(), vec![9]);
+}
+
+// ============ SharedCowVec tests ============
+
+#[cfg(feature = "arc-swap")]
+#[test]
+fn test_shared_cow_vec_snapshots_are_never_torn() {
+    use super::SharedCowVec;
+
+    const BATCH: usize = 8;
+    const VERSIONS: usize = 200;
+
+    let shared = Arc::new(SharedCowVec::new(CowVec::new()));
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || {
+                let mut last_len = 0;
+                while last_len < BATCH * VERSIONS {
+                    let snapshot = shared.snapshot();
+                    // Each version appends one full batch of its own number.
+                    assert_eq!(snapshot.len() % BATCH, 0);
+                    assert!(snapshot.len() >= last_len);
+                    for (i, &value) in snapshot.iter().enumerate() {
+                        assert_eq!(value, i / BATCH);
+                    }
+                    last_len = snapshot.len();
+                }
+            })
+        })
+        .collect();
+
+    for version in 0..VERSIONS {
+        shared.update(|vec| vec.extend(std::iter::repeat_n(version, BATCH)));
+    }
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(shared.snapshot().len(), BATCH * VERSIONS);
+}
+
+#[cfg(feature = "arc-swap")]
+#[test]
+fn test_shared_cow_vec_store_and_into_inner() {
+    use super::SharedCowVec;
+
+    let shared = SharedCowVec::from(CowVec::from(vec![1, 2]));
+    let old = shared.snapshot();
+    shared.store(CowVec::from(vec![3]));
+    assert_eq!(old, vec![1, 2]);
+    assert_eq!(shared.into_inner(), vec![3]);
+}
+
+// ============ get2_mut tests ============
+
+#[test]
+fn test_get2_mut_two_element_edit() {
+    let mut vec = CowVec::from(vec![
+        String::from("a"),
+        String::from("b"),
+        String::from("c"),
+    ]);
+    let original = vec.clone();
+    {
+        let (first, last) = vec.get2_mut(2, 0).unwrap();
+        std::mem::swap(first, last);
+        first.push('!');
+    }
+    assert_eq!(
+        vec,
+        vec!["c".to_string(), "b".to_string(), "a!".to_string()]
+    );
+    assert_eq!(
+        original,
+        vec!["a".to_string(), "b".to_string(), "c".to_string()]
+    );
+    assert_eq!(vec.diff(&original), vec![0, 2]);
+}
+
+#[test]
+fn test_get2_mut_rejects_invalid_indices() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    assert!(vec.get2_mut(1, 1).is_none());
+    assert!(vec.get2_mut(0, 3).is_none());
+    assert!(vec.get2_mut(5, 0).is_none());
+    assert!(vec.is_compact());
+}
+
+// ============ try_split_off tests ============
+
+#[test]
+fn test_try_split_off() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4]);
+    let tail = vec.try_split_off(2).unwrap();
+    assert_eq!(vec, vec![1, 2]);
+    assert_eq!(tail, vec![3, 4]);
+    assert!(tail.shares_arena_with(&vec));
+
+    let empty = vec.try_split_off(2).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(vec, vec![1, 2]);
+
+    assert!(vec.try_split_off(3).is_none());
+    assert_eq!(vec, vec![1, 2]);
+}
+
+// ============ iter_from tests ============
+
+#[test]
+fn test_iter_from_offset() {
+    let vec = CowVec::from((1..6).collect::<Vec<i32>>());
+    let iter = vec.iter_from(2);
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+}
+
+#[test]
+fn test_iter_from_clamps_past_end() {
+    let vec = CowVec::from(vec![1, 2, 3]);
+    assert_eq!(vec.iter_from(3).count(), 0);
+    let iter = vec.iter_from(10);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.count(), 0);
+    assert_eq!(vec.iter_from(0).count(), 3);
+}
+
+// ============ rev_iter / DoubleEndedIterator tests ============
+
+#[test]
+fn test_rev_iter() {
+    let vec = CowVec::from(vec![1, 2, 3, 4]);
+    let rev = vec.rev_iter();
+    assert_eq!(rev.len(), 4);
+    assert_eq!(rev.copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+    assert_eq!(CowVec::<i32>::new().rev_iter().len(), 0);
+}
+
+#[test]
+fn test_iter_double_ended_meets_in_middle() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let mut iter = vec.iter();
+    assert_eq!(iter.next(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&5));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn test_iter_from_rev() {
+    let vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let tail: Vec<i32> = vec.iter_from(3).rev().copied().collect();
+    assert_eq!(tail, vec![5, 4]);
+    let mut iter = vec.iter();
+    iter.next_back();
+    assert_eq!(iter.count(), 4);
+    let mut iter = vec.iter();
+    iter.next_back();
+    assert_eq!(iter.last(), Some(&4));
+    let mut iter = vec.iter();
+    iter.next_back();
+    assert_eq!(iter.nth(3), Some(&4));
+    assert_eq!(iter.next(), None);
+}
+
+// ============ insert_sorted tests ============
+
+#[test]
+fn test_insert_sorted_middle_and_ends() {
+    let mut vec = CowVec::from(vec![10, 20, 30]);
+    let original = vec.clone();
+    assert_eq!(vec.insert_sorted(25), 2);
+    assert_eq!(vec.insert_sorted(5), 0);
+    assert_eq!(vec.insert_sorted(40), 5);
+    assert_eq!(vec, vec![5, 10, 20, 25, 30, 40]);
+    assert_eq!(original, vec![10, 20, 30]);
+}
+
+#[test]
+fn test_insert_sorted_after_duplicates() {
+    let mut vec = CowVec::from(vec![(1, 'a'), (2, 'a'), (2, 'b'), (3, 'a')]);
+    let index = vec.insert_sorted((2, 'b'));
+    assert_eq!(index, 3);
+
+    let mut vec = CowVec::from(vec![1, 2, 2, 3]);
+    let first_two = vec.as_ptr_slice()[1];
+    assert_eq!(vec.insert_sorted(2), 3);
+    assert_eq!(vec, vec![1, 2, 2, 2, 3]);
+    assert_eq!(vec.as_ptr_slice()[1], first_two);
+
+    let mut empty = CowVec::new();
+    assert_eq!(empty.insert_sorted(1), 0);
+}
+
+// ============ remove_item tests ============
+
+#[test]
+fn test_remove_item() {
+    let mut vec = CowVec::from(vec!["a", "b", "c", "b"]);
+    let original = vec.clone();
+    assert_eq!(vec.remove_item(&"b"), Some(&"b"));
+    assert_eq!(vec, vec!["a", "c", "b"]);
+    assert_eq!(original, vec!["a", "b", "c", "b"]);
+}
+
+#[test]
+fn test_remove_item_missing() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    assert_eq!(vec.remove_item(&4), None);
+    assert_eq!(vec, vec![1, 2, 3]);
+    assert!(vec.is_structure_shared());
+    drop(clone);
+}
+
+// ============ fill_range tests ============
+
+#[test]
+fn test_fill_range_only_changes_range() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4, 5]);
+    let original = vec.clone();
+    vec.fill_range(1..=2, 9);
+    assert_eq!(vec, vec![1, 9, 9, 4, 5]);
+    assert_eq!(vec.diff(&original), vec![1, 2]);
+    assert_eq!(original, vec![1, 2, 3, 4, 5]);
+
+    vec.fill_range(.., 0);
+    assert_eq!(vec, vec![0, 0, 0, 0, 0]);
+    vec.fill_range(2..2, 7);
+    assert_eq!(vec, vec![0, 0, 0, 0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_fill_range_out_of_bounds() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    vec.fill_range(1..4, 0);
+}
+
+#[test]
+#[should_panic(expected = "starts at")]
+fn test_fill_range_inverted() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    #[allow(clippy::reversed_empty_ranges)]
+    vec.fill_range(2..1, 0);
+}
+
+// ============ enumerate_ptr tests ============
+
+#[test]
+fn test_enumerate_ptr_matches_iter_ptr() {
+    let base = CowVec::from(vec![1, 2, 3, 4]);
+    let mut edited = base.clone();
+    edited.set(2, 30);
+
+    let expected: Vec<(usize, *const i32)> = edited.iter_ptr().enumerate().collect();
+    assert_eq!(edited.enumerate_ptr().collect::<Vec<_>>(), expected);
+
+    let moved: Vec<usize> = edited
+        .enumerate_ptr()
+        .filter(|&(i, ptr)| ptr != base.as_ptr_slice()[i])
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(moved, vec![2]);
+}
+
+// ============ snapshot tests ============
+
+#[test]
+fn test_snapshot_frees_old_arena() {
+    use std::rc::Rc;
+
+    let marker = Rc::new(());
+    let mut versions = vec![CowVec::from(vec![Rc::clone(&marker), Rc::clone(&marker)])];
+    for _ in 0..10 {
+        let mut next = versions.last().unwrap().clone();
+        next.set(0, Rc::clone(&marker));
+        versions.push(next);
+    }
+    assert_eq!(Rc::strong_count(&marker), 1 + 2 + 10);
+
+    let checkpoint = versions.last().unwrap().snapshot();
+    assert!(!checkpoint.shares_arena_with(&versions[0]));
+    drop(versions);
+    assert_eq!(Rc::strong_count(&marker), 1 + 2);
+    assert_eq!(checkpoint.len(), 2);
+    assert!(checkpoint.is_compact());
+}
+
+// ============ try_get_mut tests ============
+
+#[test]
+fn test_try_get_mut_unique_does_not_allocate() {
+    let mut vec = CowVec::from(vec![String::from("a"), String::from("b")]);
+    let before = vec.memory_usage();
+    let slot = vec.as_ptr_slice()[0];
+    vec.try_get_mut(0).unwrap().push('!');
+    assert_eq!(vec, vec!["a!".to_string(), "b".to_string()]);
+    assert_eq!(vec.as_ptr_slice()[0], slot);
+    assert_eq!(vec.memory_usage(), before);
+    assert!(vec.try_get_mut(2).is_none());
+}
+
+#[test]
+fn test_try_get_mut_refuses_when_shared() {
+    let mut vec = CowVec::from(vec![1, 2, 3]);
+    let clone = vec.clone();
+    assert!(vec.try_get_mut(0).is_none());
+    drop(clone);
+    assert!(vec.try_get_mut(0).is_some());
+
+    let handle = vec.handle(1).unwrap();
+    assert!(vec.try_get_mut(0).is_none());
+    drop(handle);
+
+    vec.copy_within(0..1, 2);
+    assert!(vec.try_get_mut(0).is_none());
+    assert!(vec.try_get_mut(1).is_some());
+}
+
+// ============ arena_stats tests ============
+
+#[test]
+fn test_arena_stats_after_push_and_pop() {
+    let mut vec = CowVec::new();
+    for i in 0..5 {
+        vec.push(i);
+    }
+    vec.pop();
+    vec.pop();
+    let stats = vec.arena_stats();
+    assert_eq!(stats.live, 3);
+    assert_eq!(stats.allocated, 5);
+    assert_eq!(stats.garbage, 2);
+    assert!(!stats.arena_shared);
+
+    let clone = vec.clone();
+    assert!(vec.arena_stats().arena_shared);
+    drop(clone);
+    assert!(format!("{:?}", vec.arena_stats()).contains("garbage: 2"));
+}
+
+// ============ retain_indexed tests ============
+
+#[test]
+fn test_retain_indexed_keeps_even_indices() {
+    let mut vec = CowVec::from(vec![10, 11, 12, 13, 14, 15]);
+    let mut seen = Vec::new();
+    vec.retain_indexed(|i, &x| {
+        seen.push((i, x));
+        i % 2 == 0
+    });
+    assert_eq!(vec, vec![10, 12, 14]);
+    assert_eq!(seen, (0..6).map(|i| (i, 10 + i as i32)).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_retain_indexed_clone_isolation() {
+    let mut vec = CowVec::from(vec![1, 2, 3, 4]);
+    let clone = vec.clone();
+    vec.retain_indexed(|i, _| i >= 2);
+    assert_eq!(vec, vec![3, 4]);
+    assert_eq!(clone, vec![1, 2, 3, 4]);
+}